@@ -3,8 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use licium_cw721::msg::{ExecuteMsg, InstantiateMsg, MintMsg, QueryMsg, TokenResponse};
-use licium_cw721::state::{ IsccData, Licensing, License };
+use licium_cw721::msg::{
+    AdminResponse, DepositHoldingsResponse, EarliestRegistrationResponse, ExecuteMsg,
+    InstantiateMsg, IsLicensedResponse, LicensesResponse, LicenseSupplyResponse, ListingsResponse,
+    MintMsg, ModerationLogResponse, QueryMsg, QuoteResponse, StatsResponse, TokenResponse,
+    TokensInfoResponse, TokenStatsResponse,
+};
+use licium_cw721::state::{ Auction, Config, Deposit, Dispute, Escrow, Exclusivity, IsccData, Licensing, License, Moderation, PauseFlags };
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,7 +21,27 @@ fn main() {
     export_schema(&schema_for!(MintMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(TokenResponse), &out_dir);
+    export_schema(&schema_for!(TokensInfoResponse), &out_dir);
+    export_schema(&schema_for!(ListingsResponse), &out_dir);
+    export_schema(&schema_for!(LicensesResponse), &out_dir);
+    export_schema(&schema_for!(ModerationLogResponse), &out_dir);
+    export_schema(&schema_for!(EarliestRegistrationResponse), &out_dir);
+    export_schema(&schema_for!(AdminResponse), &out_dir);
+    export_schema(&schema_for!(DepositHoldingsResponse), &out_dir);
+    export_schema(&schema_for!(StatsResponse), &out_dir);
+    export_schema(&schema_for!(TokenStatsResponse), &out_dir);
+    export_schema(&schema_for!(LicenseSupplyResponse), &out_dir);
+    export_schema(&schema_for!(IsLicensedResponse), &out_dir);
+    export_schema(&schema_for!(QuoteResponse), &out_dir);
+    export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(IsccData), &out_dir);
     export_schema(&schema_for!(Licensing), &out_dir);
     export_schema(&schema_for!(License), &out_dir);
+    export_schema(&schema_for!(Auction), &out_dir);
+    export_schema(&schema_for!(Exclusivity), &out_dir);
+    export_schema(&schema_for!(Escrow), &out_dir);
+    export_schema(&schema_for!(Dispute), &out_dir);
+    export_schema(&schema_for!(Deposit), &out_dir);
+    export_schema(&schema_for!(Moderation), &out_dir);
+    export_schema(&schema_for!(PauseFlags), &out_dir);
 }
@@ -1,11 +1,13 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    to_binary, BankMsg, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order, Pair, 
-    Response, StdError, StdResult,
+    coin, to_binary, to_vec, Addr, Api, BankMsg, Binary, BlockInfo, Coin, Decimal, Deps, DepsMut,
+    Env, Event, MessageInfo, Order, Pair, Response, StdError, StdResult, Storage, Uint128,
 };
 
-use cw0::maybe_addr;
+use cw0::{maybe_addr, Duration};
 use cw2::set_contract_version;
 use cw721::{
     AllNftInfoResponse, ApprovedForAllResponse, ContractInfoResponse, Expiration, NftInfoResponse, 
@@ -16,26 +18,42 @@ use cw721_base::contract::{
     execute_approve, execute_revoke, execute_approve_all, execute_revoke_all, execute_transfer_nft,
     execute_send_nft
 };
-use cw721_base::ContractError; // TODO use custom errors instead
-use cw721_base::state::{Approval, CONTRACT_INFO, increment_tokens, num_tokens, OPERATORS, TokenInfo, tokens};
-use cw_storage_plus::Bound;
+use cw721_base::state::{
+    Approval, CONTRACT_INFO, increment_tokens, num_tokens, OPERATORS, TOKEN_COUNT, TokenInfo, tokens,
+};
+use cw_storage_plus::{Bound, PrimaryKey};
 
-use crate::msg::{InstantiateMsg, ExecuteMsg, MintMsg, QueryMsg, TokenResponse};
-use crate::state::{IsccData, ISCC_DATA, ISCC, License, LICENSE, Licensing, LICENSING };
+use crate::crypto::{secp256k1_address, sha256};
+use crate::error::ContractError;
+use crate::iscc::IsccCode;
+use crate::pagination::{id_key, raw_key, Pagination, DEFAULT_SCAN_LIMIT, MAX_SCAN_LIMIT};
+use crate::msg::{
+    AdminResponse, DepositHoldingsResponse, DisputeRuling, EarliestRegistrationResponse, ExecuteMsg,
+    InstantiateMsg, IsccUnit, IsLicensedResponse, LicenseItem, LicensesResponse,
+    LicenseSupplyResponse, LicensingStatus, ListingsResponse, MintFeeMsg, MintMsg, MintVoucher,
+    ModerationLogResponse, Payout, QueryMsg, QuoteResponse, RegistrationMatch, StatsResponse, TokenResponse,
+    TokensInfoResponse, TokenStatsResponse,
+};
+use crate::state::{
+    Auction, AUCTIONS, Authorship, Bid, BlockStamp, Config, CONFIG, Escrow, ESCROWS, ExclusiveTerms, Exclusivity, EXCLUSIVITY, IsccData, iscc_data, ISCC,
+    License, licenses, LICENSES_SOLD, Licensing, LICENSING, Listing, LISTINGS, Revocation, Royalty,
+    ROYALTIES, Stats, STATS, TOKEN_STATS, Dispute, DISPUTES, Moderation, MODERATION,
+    VOUCHER_NONCES, ModerationEntry, SignatureScheme, MODERATION_COUNT, MODERATION_LOG, PauseFlags, PAUSE, ReasonCode, Deposit,
+    DEPOSITS, DEPOSITS_HELD, MintDeposit, MintFee,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:licium-cw721";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// used for limiting queries
-const DEFAULT_LIMIT: u32 = 10;
-const MAX_LIMIT: u32 = 30;
+// used for limiting batch mints when not configured
+const DEFAULT_MAX_MINT_BATCH: u32 = 50;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -44,61 +62,418 @@ pub fn instantiate(
         symbol: msg.symbol,
     };
     CONTRACT_INFO.save(deps.storage, &contract_info)?;
+    let arbitrators = msg
+        .arbitrators
+        .unwrap_or_default()
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<_>>()?;
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+    let moderators = msg
+        .moderators
+        .unwrap_or_default()
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<_>>()?;
+    let config = Config {
+        admin: Some(admin),
+        pending_admin: None,
+        moderators,
+        max_mint_batch: msg.max_mint_batch.unwrap_or(DEFAULT_MAX_MINT_BATCH),
+        arbitrators,
+        min_dispute_bond: msg.min_dispute_bond,
+        mint_deposit: msg.mint_deposit,
+        mint_fee: msg.mint_fee.map(|fee| validate_mint_fee(deps.api, fee)).transpose()?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    STATS.save(deps.storage, &Stats::default())?;
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    if is_paused(&pause, &msg) {
+        return Err(ContractError::Paused {});
+    }
+
     match msg {
         ExecuteMsg::Mint(msg) => execute_mint(deps, env, info, msg),
+        ExecuteMsg::MintBatch(msgs) => execute_mint_batch(deps, env, info, msgs),
         ExecuteMsg::License {
             token_id,
-        } => execute_licensing(deps, info, token_id),
+            licensee,
+        } => execute_licensing(deps, env, info, token_id, licensee),
+        ExecuteMsg::LicenseBatch {
+            items,
+        } => execute_license_batch(deps, env, info, items),
+        ExecuteMsg::GrantLicense {
+            token_id,
+            licensee,
+            price,
+            expires,
+            memo,
+        } => execute_grant_license(deps, env, info, token_id, licensee, price, expires, memo),
+        ExecuteMsg::RevokeLicense {
+            token_id,
+            licensee,
+            reason,
+            refund,
+        } => execute_revoke_license(deps, info, token_id, licensee, reason, refund),
+        ExecuteMsg::UpdateLicensing {
+            token_id,
+            url,
+            price,
+            exclusive,
+            max_licenses,
+            refund_window,
+        } => execute_update_licensing(
+            deps,
+            env,
+            info,
+            token_id,
+            url,
+            price,
+            exclusive,
+            max_licenses,
+            refund_window,
+        ),
+        ExecuteMsg::RequestRefund {
+            token_id,
+        } => execute_request_refund(deps, env, info, token_id),
+        ExecuteMsg::ReleaseEscrow {
+            token_id,
+            licensee,
+        } => execute_release_escrow(deps, env, token_id, licensee),
         ExecuteMsg::Approve { 
             spender,
             token_id, 
             expires,
-        } => execute_approve(deps, env, info, spender, token_id, expires),
+        } => Ok(execute_approve(deps, env, info, spender, token_id, expires)?),
         ExecuteMsg::Revoke { 
             spender, 
             token_id,
-        } => execute_revoke(deps, env, info, spender, token_id),
+        } => Ok(execute_revoke(deps, env, info, spender, token_id)?),
         ExecuteMsg::ApproveAll { 
             operator, 
             expires,
-        } => execute_approve_all(deps, env, info, operator, expires),
+        } => Ok(execute_approve_all(deps, env, info, operator, expires)?),
         ExecuteMsg::RevokeAll { 
             operator 
-        } => execute_revoke_all(deps, env, info, operator),
+        } => Ok(execute_revoke_all(deps, env, info, operator)?),
         ExecuteMsg::TransferNft { 
             recipient, 
             token_id, 
-        } => execute_transfer_nft(deps, env, info, recipient, token_id),
+        } => {
+            assert_transferable(deps.storage, &env.block, &token_id)?;
+            // a transferred token can no longer be sold by the previous owner
+            LISTINGS.remove(deps.storage, &token_id);
+            let previous = tokens().load(deps.storage, &token_id)?.owner;
+            let res = execute_transfer_nft(deps.branch(), env, info, recipient, token_id.clone())?;
+            record_owner_change(deps.storage, Some(&previous), &token_id)?;
+            Ok(res)
+        },
         ExecuteMsg::SendNft { 
             contract, 
             token_id, 
             msg,
-        } => execute_send_nft(deps, env, info, contract, token_id, msg),
+        } => {
+            assert_transferable(deps.storage, &env.block, &token_id)?;
+            LISTINGS.remove(deps.storage, &token_id);
+            let previous = tokens().load(deps.storage, &token_id)?.owner;
+            let res = execute_send_nft(deps.branch(), env, info, contract, token_id.clone(), msg)?;
+            record_owner_change(deps.storage, Some(&previous), &token_id)?;
+            Ok(res)
+        },
+        ExecuteMsg::ListForSale {
+            token_id,
+            price,
+        } => execute_list_for_sale(deps, info, token_id, price),
+        ExecuteMsg::CancelListing {
+            token_id,
+        } => execute_cancel_listing(deps, info, token_id),
+        ExecuteMsg::Buy {
+            token_id,
+        } => execute_buy(deps, env, info, token_id),
+        ExecuteMsg::StartAuction {
+            token_id,
+            reserve_price,
+            min_increment,
+            end,
+            license_duration,
+        } => execute_start_auction(
+            deps,
+            env,
+            info,
+            token_id,
+            reserve_price,
+            min_increment,
+            end,
+            license_duration,
+        ),
+        ExecuteMsg::PlaceBid {
+            token_id,
+        } => execute_place_bid(deps, env, info, token_id),
+        ExecuteMsg::SettleAuction {
+            token_id,
+        } => execute_settle_auction(deps, env, token_id),
+        ExecuteMsg::CancelAuction {
+            token_id,
+        } => execute_cancel_auction(deps, info, token_id),
+        ExecuteMsg::OpenDispute {
+            token_id,
+            evidence_uri,
+            bond,
+        } => execute_open_dispute(deps, env, info, token_id, evidence_uri, bond),
+        ExecuteMsg::ResolveDispute {
+            token_id,
+            ruling,
+        } => execute_resolve_dispute(deps, info, token_id, ruling),
+        ExecuteMsg::Moderate {
+            token_id,
+            hidden,
+            suspended,
+            reason,
+            forfeit_deposit,
+        } => execute_moderate(
+            deps,
+            env,
+            info,
+            token_id,
+            hidden,
+            suspended,
+            reason,
+            forfeit_deposit.unwrap_or(false),
+        ),
+        ExecuteMsg::ReclaimDeposit {
+            token_id,
+        } => execute_reclaim_deposit(deps, env, info, token_id),
+        ExecuteMsg::Burn {
+            token_id,
+        } => execute_burn(deps, env, info, token_id),
+        ExecuteMsg::SetMintDeposit {
+            mint_deposit,
+        } => execute_set_mint_deposit(deps, info, mint_deposit),
+        ExecuteMsg::SetMintFee {
+            mint_fee,
+        } => execute_set_mint_fee(deps, info, mint_fee),
+        ExecuteMsg::UpdateModerators {
+            add,
+            remove,
+        } => execute_update_moderators(deps, info, add, remove),
+        ExecuteMsg::UpdateArbitrators {
+            add,
+            remove,
+        } => execute_update_arbitrators(deps, info, add, remove),
+        ExecuteMsg::ProposeAdmin {
+            admin,
+        } => execute_propose_admin(deps, info, admin),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, info),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, info),
+        ExecuteMsg::SetPause(flags) => execute_set_pause(deps, info, flags),
+        ExecuteMsg::MintAndLicense {
+            voucher,
+            signature,
+            licensee,
+        } => execute_mint_and_license(deps, env, info, voucher, signature, licensee),
+    }
+}
+
+/// Whether the message belongs to a paused group of operations
+fn is_paused(pause: &PauseFlags, msg: &ExecuteMsg) -> bool {
+    match msg {
+        ExecuteMsg::Mint(_) | ExecuteMsg::MintBatch(_) => pause.mint,
+        ExecuteMsg::MintAndLicense { .. } => pause.mint || pause.license,
+        ExecuteMsg::License { .. }
+        | ExecuteMsg::LicenseBatch { .. }
+        | ExecuteMsg::GrantLicense { .. }
+        | ExecuteMsg::StartAuction { .. }
+        | ExecuteMsg::PlaceBid { .. }
+        | ExecuteMsg::SettleAuction { .. } => pause.license,
+        ExecuteMsg::TransferNft { .. }
+        | ExecuteMsg::SendNft { .. }
+        | ExecuteMsg::Buy { .. }
+        | ExecuteMsg::Burn { .. } => pause.transfer,
+        ExecuteMsg::Approve { .. }
+        | ExecuteMsg::Revoke { .. }
+        | ExecuteMsg::ApproveAll { .. }
+        | ExecuteMsg::RevokeAll { .. } => pause.approvals,
+        _ => false,
     }
 }
 
 pub fn execute_mint(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: MintMsg,
 ) -> Result<Response, ContractError> {
+    let token_id = msg.token_id.clone();
+    let name = msg.name.clone();
+    let content_id = msg.content_id.clone();
+    let owner = msg.owner.clone();
+    let config = CONFIG.load(deps.storage)?;
+    must_pay_all(&info.funds, &mint_funds(&config, 1)?)?;
+    mint_token(deps.branch(), &env, &info, &config, msg)?;
+
+    // update tokens count
+    increment_tokens(deps.storage)?;
+
+    let (fee_msg, fee) = mint_fee_payment(&config, 1)?;
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "mint")
+        .add_attribute("token_id", token_id)
+        .add_attribute("name", name)
+        .add_attribute("content_id", content_id)
+        .add_attribute("owner", owner)
+        .add_attribute("fee", fee)
+    )
+}
+
+pub fn execute_mint_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<MintMsg>,
+) -> Result<Response, ContractError> {
+    if msgs.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    if msgs.len() > config.max_mint_batch as usize {
+        return Err(ContractError::BatchTooLarge { max: config.max_mint_batch });
+    }
+
+    let count = msgs.len() as u64;
+    must_pay_all(&info.funds, &mint_funds(&config, count)?)?;
+
+    // tokens are minted one by one, so duplicates inside the batch are claimed like stored ones
+    for msg in msgs {
+        mint_token(deps.branch(), &env, &info, &config, msg)?;
+    }
+
+    // update tokens count once for the whole batch
+    let total = num_tokens(deps.storage)? + count;
+    TOKEN_COUNT.save(deps.storage, &total)?;
+
+    let (fee_msg, fee) = mint_fee_payment(&config, count)?;
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "mint_batch")
+        .add_attribute("count", count.to_string())
+        .add_attribute("minter", info.sender)
+        .add_attribute("fee", fee)
+    )
+}
+
+pub fn execute_mint_and_license(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voucher: MintVoucher,
+    signature: Binary,
+    licensee: Option<String>,
+) -> Result<Response, ContractError> {
+    // accounts derive from the compressed key, an uncompressed one would map the creator to
+    // another address with fresh nonces
+    if voucher.creator_pubkey.len() != 33 {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    // the creator signs the voucher as serialized in the message, malformed keys fail as well
+    let hash = sha256(&to_vec(&voucher)?);
+    let verified = deps.api.secp256k1_verify(&hash, &signature, &voucher.creator_pubkey);
+    if !matches!(verified, Ok(true)) {
+        return Err(ContractError::InvalidSignature {});
+    }
+    if voucher.contract_address != env.contract.address {
+        return Err(ContractError::WrongContract {});
+    }
+    if let Some(expires) = voucher.expires {
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::VoucherExpired {});
+        }
+    }
+    let creator = secp256k1_address(deps.api, &voucher.creator_pubkey)?;
+    if VOUCHER_NONCES.has(deps.storage, (&creator, voucher.nonce.into())) {
+        return Err(ContractError::NonceUsed {});
+    }
+    VOUCHER_NONCES.save(deps.storage, (&creator, voucher.nonce.into()), &true)?;
+
+    let licensee = match licensee {
+        Some(licensee) => deps.api.addr_validate(&licensee)?,
+        None => info.sender.clone(),
+    };
+
+    // mint to the creator, as if it had sent the mint itself
+    let config = CONFIG.load(deps.storage)?;
+    let token_id = voucher.mint.token_id.clone();
+    let creator_info = MessageInfo {
+        sender: creator.clone(),
+        funds: vec![],
+    };
+    mint_token(deps.branch(), &env, &creator_info, &config, voucher.mint)?;
+    increment_tokens(deps.storage)?;
+
+    // the deposit is paid by the sender, so it is refunded to the sender as well
+    if let Some(mut deposit) = DEPOSITS.may_load(deps.storage, &token_id)? {
+        deposit.depositor = info.sender.clone();
+        DEPOSITS.save(deps.storage, &token_id, &deposit)?;
+    }
+
+    // the sender covers the minting costs along with the license
+    let purchase = prepare_license(deps.storage, &env.block, &token_id, &licensee)?;
+    let mut required = mint_funds(&config, 1)?;
+    add_coin(&mut required, &purchase.licensing.price);
+    must_pay_all(&info.funds, &required)?;
+    let price = purchase.licensing.price.clone();
+    let payout = record_license(deps.storage, &env.block, purchase, &info.sender)?;
+
+    let (mut messages, fee) = mint_fee_payment(&config, 1)?;
+    if let Some((owner, amount)) = payout {
+        messages.push(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: vec![amount],
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "mint_and_license")
+        .add_attribute("token_id", token_id)
+        .add_attribute("creator", creator)
+        .add_attribute("fee", fee)
+        .add_attribute("price", price.to_string())
+        .add_attribute("licensee", licensee)
+        .add_attribute("payer", info.sender))
+}
+
+/// Stores a new token with its ISCC, licensing and royalty data
+fn mint_token(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    msg: MintMsg,
+) -> Result<(), ContractError> {
+    if let Some(authorship) = &msg.authorship {
+        verify_authorship(deps.api, authorship, &msg)?;
+    }
+
     // create the token
     let token = TokenInfo {
         name: msg.name.clone(),
         description: msg.description.clone(),
         image: Some(msg.image.clone()),
-        owner: info.sender,
+        owner: info.sender.clone(),
         approvals: vec![],
     };
     tokens().update(
@@ -109,92 +484,1581 @@ pub fn execute_mint(
             None => Ok(token),
         }
     )?;
-    
-    // update tokens count
-    increment_tokens(deps.storage)?;
+    record_owner_change(deps.storage, None, &msg.token_id)?;
+
+    // store iscc data related to the token
+    let iscc = IsccData {
+        token_id: msg.token_id.clone(),
+        meta_id: msg.meta_id.clone(),
+        content_id: msg.content_id.clone(),
+        data_id: msg.data_id.clone(),
+        instance_id: msg.instance_id.clone(),
+        tophash: msg.tophash.clone(),
+        registrant: info.sender.clone(),
+        registered_at: BlockStamp::from(&env.block),
+        authorship: msg.authorship.clone(),
+    };
+    iscc_data().save(deps.storage, &msg.token_id, &iscc)?;
+
+    // associate iscc content id with token
+    ISCC.update(
+        deps.storage, 
+        &msg.content_id, 
+        | old | match old {
+            Some(_) => Err(ContractError::Claimed {}),
+            None => Ok(msg.token_id.clone())
+        }
+    )?;
+
+    // store licensing data
+    let licensing = Licensing {
+        token_id: msg.token_id.clone(),
+        url: msg.license_url.clone(),
+        price: msg.license_price,
+        exclusive: msg.license_exclusive,
+        max_licenses: msg.max_licenses,
+        refund_window: msg.refund_window,
+    };
+    LICENSING.save(deps.storage, &msg.token_id, &licensing)?;
+
+    // store royalty data
+    if let Some(royalty) = msg.royalty {
+        if royalty.share > Decimal::one() {
+            return Err(ContractError::InvalidRoyalty {});
+        }
+        let royalty = Royalty {
+            recipient: deps.api.addr_validate(&royalty.recipient)?,
+            share: royalty.share,
+        };
+        ROYALTIES.save(deps.storage, &msg.token_id, &royalty)?;
+    }
+
+    // hold the deposit until the token proves clean
+    if let Some(mint_deposit) = &config.mint_deposit {
+        let deposit = Deposit {
+            token_id: msg.token_id.clone(),
+            depositor: info.sender.clone(),
+            amount: mint_deposit.amount.clone(),
+            refundable_at: mint_deposit.clean_period.after(&env.block),
+        };
+        DEPOSITS.save(deps.storage, &msg.token_id, &deposit)?;
+        update_deposits_held(deps.storage, &deposit.amount, true)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the authorship proof signs the full ISCC code of the minted work
+fn verify_authorship(api: &dyn Api, authorship: &Authorship, msg: &MintMsg) -> Result<(), ContractError> {
+    let code = format!("{}-{}-{}-{}", msg.meta_id, msg.content_id, msg.data_id, msg.instance_id);
+    let verified = match authorship.scheme {
+        SignatureScheme::Secp256k1 => {
+            api.secp256k1_verify(&sha256(code.as_bytes()), &authorship.signature, &authorship.pubkey)
+        },
+        SignatureScheme::Ed25519 => {
+            api.ed25519_verify(code.as_bytes(), &authorship.signature, &authorship.pubkey)
+        },
+    };
+    // malformed keys or signatures fail like wrong ones
+    match verified {
+        Ok(true) => Ok(()),
+        _ => Err(ContractError::InvalidAuthorship {}),
+    }
+}
+
+/// Funds to send along when minting `count` tokens: their deposits and fees
+fn mint_funds(config: &Config, count: u64) -> StdResult<Vec<Coin>> {
+    let mut funds = vec![];
+    if let Some(mint_deposit) = &config.mint_deposit {
+        add_coin(&mut funds, &multiply_coin(&mint_deposit.amount, count)?);
+    }
+    if let Some(mint_fee) = &config.mint_fee {
+        add_coin(&mut funds, &multiply_coin(&mint_fee.amount, count)?);
+    }
+    Ok(funds)
+}
+
+/// Message forwarding the fees of `count` minted tokens to the treasury, with the fee charged
+fn mint_fee_payment(config: &Config, count: u64) -> StdResult<(Vec<BankMsg>, String)> {
+    match &config.mint_fee {
+        Some(mint_fee) if !mint_fee.amount.amount.is_zero() => {
+            let fee = multiply_coin(&mint_fee.amount, count)?;
+            let fee_msg = BankMsg::Send {
+                to_address: mint_fee.treasury.to_string(),
+                amount: vec![fee.clone()],
+            };
+            Ok((vec![fee_msg], fee.to_string()))
+        },
+        _ => Ok((vec![], "none".to_string())),
+    }
+}
+
+fn multiply_coin(amount: &Coin, count: u64) -> StdResult<Coin> {
+    let total = amount.amount.checked_mul(count.into()).map_err(StdError::overflow)?;
+    Ok(coin(total.u128(), &amount.denom))
+}
+
+fn validate_mint_fee(api: &dyn Api, fee: MintFeeMsg) -> StdResult<MintFee> {
+    Ok(MintFee {
+        amount: fee.amount,
+        treasury: api.addr_validate(&fee.treasury)?,
+    })
+}
+
+/// Adds or removes a deposit from the total held by the contract
+fn update_deposits_held(storage: &mut dyn Storage, amount: &Coin, add: bool) -> StdResult<()> {
+    let mut held = DEPOSITS_HELD.may_load(storage)?.unwrap_or_default();
+    if add {
+        add_coin(&mut held, amount);
+    } else {
+        sub_coin(&mut held, amount)?;
+    }
+    DEPOSITS_HELD.save(storage, &held)
+}
+
+pub fn execute_licensing(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    licensee: Option<String>,
+) -> Result<Response, ContractError> {
+    // the sender pays, but may license on behalf of someone else
+    let licensee = match licensee {
+        Some(licensee) => deps.api.addr_validate(&licensee)?,
+        None => info.sender.clone(),
+    };
+
+    let purchase = prepare_license(deps.storage, &env.block, &token_id, &licensee)?;
+    must_pay(&info.funds, &purchase.licensing.price)?;
+    let price = purchase.licensing.price.clone();
+    let payout = record_license(deps.storage, &env.block, purchase, &info.sender)?;
+
+    let mut messages = vec![];
+    if let Some((owner, amount)) = payout {
+        messages.push(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: vec![amount],
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "license")
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", price.to_string())
+        .add_attribute("licensee", licensee)
+        .add_attribute("payer", info.sender))
+}
+
+pub fn execute_license_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<LicenseItem>,
+) -> Result<Response, ContractError> {
+    if items.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+
+    // licenses are recorded one by one, so every item is checked against the previous ones
+    let mut required: Vec<Coin> = vec![];
+    let mut payouts: BTreeMap<Addr, Vec<Coin>> = BTreeMap::new();
+    let mut events = vec![];
+    for item in items {
+        let licensee = match item.licensee {
+            Some(licensee) => deps.api.addr_validate(&licensee)?,
+            None => info.sender.clone(),
+        };
+        let purchase = prepare_license(deps.storage, &env.block, &item.token_id, &licensee)?;
+        let price = purchase.licensing.price.clone();
+        add_coin(&mut required, &price);
+        if let Some((owner, amount)) = record_license(deps.storage, &env.block, purchase, &info.sender)? {
+            add_coin(payouts.entry(owner).or_default(), &amount);
+        }
+        events.push(Event::new("license")
+            .add_attribute("token_id", item.token_id)
+            .add_attribute("price", price.to_string())
+            .add_attribute("licensee", licensee));
+    }
+    must_pay_all(&info.funds, &required)?;
+    let count = events.len();
+
+    // one payment per owner, whatever the number of tokens licensed from them
+    let messages: Vec<BankMsg> = payouts
+        .into_iter()
+        .map(|(owner, amount)| BankMsg::Send {
+            to_address: owner.to_string(),
+            amount,
+        })
+        .collect();
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(events)
+        .add_attribute("action", "license_batch")
+        .add_attribute("count", count.to_string())
+        .add_attribute("payer", info.sender))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_grant_license(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    licensee: String,
+    price: Option<Coin>,
+    expires: Option<Expiration>,
+    memo: Option<String>,
+) -> Result<Response, ContractError> {
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    check_can_send(deps.as_ref(), &env, &info, &token_info)?;
+
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    check_licensable(deps.storage, &env.block, &licensing)?;
+    if let Some(expires) = expires {
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::LicenseExpired {});
+        }
+    }
+    let licensee = deps.api.addr_validate(&licensee)?;
+    check_licensee(deps.storage, &env.block, &token_id, &licensee)?;
+
+    // granted licenses count against the license supply like sold ones
+    record_license_sale(deps.storage, &licensing)?;
+
+    // grants follow the exclusive terms, but may set their own expiration
+    let expires = match &licensing.exclusive {
+        Some(terms) => {
+            let expires = expires.unwrap_or_else(|| terms.duration.after(&env.block));
+            let exclusivity = Exclusivity {
+                licensee: licensee.clone(),
+                expires,
+                lock_transfer: terms.lock_transfer,
+            };
+            EXCLUSIVITY.save(deps.storage, &token_id, &exclusivity)?;
+            expires
+        },
+        None => expires.unwrap_or(Expiration::Never {}),
+    };
+
+    let price = price.unwrap_or_else(|| coin(0, &licensing.price.denom));
+    let license = License {
+        token_id: token_id.clone(),
+        licensee: licensee.clone(),
+        payer: info.sender.clone(),
+        price: price.clone(),
+        expires,
+        exclusive: licensing.exclusive.is_some(),
+        granted: true,
+        memo,
+        revoked: None,
+        purchased_at: BlockStamp::from(&env.block),
+    };
+    save_new_license(deps.storage, &env.block, &license)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_license")
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", price.to_string())
+        .add_attribute("licensee", licensee)
+        .add_attribute("grantor", info.sender))
+}
+
+pub fn execute_revoke_license(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    licensee: String,
+    reason: String,
+    refund: bool,
+) -> Result<Response, ContractError> {
+    // only the owner can revoke licenses
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let licensee = deps.api.addr_validate(&licensee)?;
+    let mut license = licenses().load(deps.storage, (&licensee, &token_id))?;
+    if license.revoked.is_some() {
+        return Err(ContractError::LicenseRevoked {});
+    }
+    if refund && license.granted {
+        return Err(ContractError::NotRefundable {});
+    }
+
+    // the refund goes to whoever paid for the license, taken from the escrowed payment
+    // if it was not released yet, otherwise paid by the owner
+    let mut messages = vec![];
+    let escrow = ESCROWS.may_load(deps.storage, (&licensee, &token_id))?;
+    if escrow.is_none() && refund && !license.price.amount.is_zero() {
+        must_pay(&info.funds, &license.price)?;
+        messages.push(BankMsg::Send {
+            to_address: license.payer.to_string(),
+            amount: vec![license.price.clone()],
+        });
+    } else {
+        must_pay_all(&info.funds, &[])?;
+    }
+    if let Some(escrow) = &escrow {
+        ESCROWS.remove(deps.storage, (&licensee, &token_id));
+        let to_address = if refund { &escrow.payer } else { &escrow.payee };
+        messages.push(BankMsg::Send {
+            to_address: to_address.to_string(),
+            amount: vec![escrow.amount.clone()],
+        });
+    }
+
+    // keep the record, marked as revoked
+    license.revoked = Some(Revocation {
+        reason: reason.clone(),
+        refunded: refund,
+    });
+    licenses().save(deps.storage, (&licensee, &token_id), &license)?;
+    if refund {
+        save_refunded_license(deps.storage, &license)?;
+    }
+
+    // a revoked exclusive license no longer locks out other licensees
+    if let Some(exclusivity) = EXCLUSIVITY.may_load(deps.storage, &token_id)? {
+        if exclusivity.licensee == licensee {
+            EXCLUSIVITY.remove(deps.storage, &token_id);
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "revoke_license")
+        .add_attribute("token_id", token_id)
+        .add_attribute("licensee", licensee)
+        .add_attribute("reason", reason)
+        .add_attribute("refund", refund.to_string()))
+}
+
+pub fn execute_request_refund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let escrow = ESCROWS
+        .may_load(deps.storage, (&info.sender, &token_id))?
+        .ok_or(ContractError::NoEscrow {})?;
+    if escrow.release_at.is_expired(&env.block) {
+        return Err(ContractError::RefundWindowClosed {});
+    }
+    ESCROWS.remove(deps.storage, (&info.sender, &token_id));
+
+    // the refund undoes the purchase, the licensee may buy the license again later
+    let license = licenses().load(deps.storage, (&info.sender, &token_id))?;
+    licenses().remove(deps.storage, (&info.sender, &token_id))?;
+    save_refunded_license(deps.storage, &license)?;
+    if let Some(exclusivity) = EXCLUSIVITY.may_load(deps.storage, &token_id)? {
+        if exclusivity.licensee == info.sender {
+            EXCLUSIVITY.remove(deps.storage, &token_id);
+        }
+    }
+
+    let refund_msg = BankMsg::Send {
+        to_address: escrow.payer.to_string(),
+        amount: vec![escrow.amount.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(refund_msg)
+        .add_attribute("action", "request_refund")
+        .add_attribute("token_id", token_id)
+        .add_attribute("licensee", info.sender)
+        .add_attribute("amount", escrow.amount.to_string()))
+}
+
+pub fn execute_release_escrow(
+    deps: DepsMut,
+    env: Env,
+    token_id: String,
+    licensee: String,
+) -> Result<Response, ContractError> {
+    let licensee = deps.api.addr_validate(&licensee)?;
+    let escrow = ESCROWS
+        .may_load(deps.storage, (&licensee, &token_id))?
+        .ok_or(ContractError::NoEscrow {})?;
+    if !escrow.release_at.is_expired(&env.block) {
+        return Err(ContractError::RefundWindowOpen {});
+    }
+    ESCROWS.remove(deps.storage, (&licensee, &token_id));
+
+    let send_funds_msg = BankMsg::Send {
+        to_address: escrow.payee.to_string(),
+        amount: vec![escrow.amount.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(send_funds_msg)
+        .add_attribute("action", "release_escrow")
+        .add_attribute("token_id", token_id)
+        .add_attribute("licensee", licensee)
+        .add_attribute("payee", escrow.payee)
+        .add_attribute("amount", escrow.amount.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_licensing(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    url: String,
+    price: Coin,
+    exclusive: Option<ExclusiveTerms>,
+    max_licenses: Option<u64>,
+    refund_window: Option<Duration>,
+) -> Result<Response, ContractError> {
+    // only the owner can change the licensing terms
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    // the terms stay put until the dispute or suspension is lifted
+    assert_not_frozen(deps.storage, &token_id)?;
+    // licenses sold so far must run out before licensing turns exclusive
+    if exclusive.is_some() && has_valid_license(deps.storage, &env.block, &token_id)? {
+        return Err(ContractError::LicensesOutstanding {});
+    }
+
+    let licensing = Licensing {
+        token_id: token_id.clone(),
+        url,
+        price: price.clone(),
+        exclusive,
+        max_licenses,
+        refund_window,
+    };
+    LICENSING.save(deps.storage, &token_id, &licensing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_licensing")
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", price.to_string())
+        .add_attribute("exclusive", licensing.exclusive.is_some().to_string()))
+}
+
+pub fn execute_list_for_sale(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    price: Coin,
+) -> Result<Response, ContractError> {
+    // only the owner can sell the token
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if price.amount.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    assert_not_frozen(deps.storage, &token_id)?;
+
+    let listing = Listing {
+        token_id: token_id.clone(),
+        seller: info.sender.clone(),
+        price: price.clone(),
+    };
+    LISTINGS.save(deps.storage, &token_id, &listing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list_for_sale")
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", price.to_string())
+        .add_attribute("seller", info.sender))
+}
+
+pub fn execute_cancel_listing(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let listing = LISTINGS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NotListed {})?;
+
+    // the current owner can also withdraw a listing left behind by a previous owner
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if listing.seller != info.sender && token_info.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    LISTINGS.remove(deps.storage, &token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_listing")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let listing = LISTINGS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NotListed {})?;
+    assert_transferable(deps.storage, &env.block, &token_id)?;
+
+    let mut token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner != listing.seller {
+        return Err(ContractError::StaleListing {});
+    }
+    must_pay(&info.funds, &listing.price)?;
+
+    // transfer ownership to the buyer, approvals granted by the seller don't carry over
+    token_info.owner = info.sender.clone();
+    token_info.approvals = vec![];
+    tokens().save(deps.storage, &token_id, &token_info)?;
+    LISTINGS.remove(deps.storage, &token_id);
+    record_owner_change(deps.storage, Some(&listing.seller), &token_id)?;
+
+    // pay royalty and send the remaining funds to the seller
+    let mut messages = vec![];
+    let mut seller_amount = listing.price.amount;
+    if let Some(royalty) = ROYALTIES.may_load(deps.storage, &token_id)? {
+        let royalty_amount = listing.price.amount * royalty.share;
+        if !royalty_amount.is_zero() && royalty.recipient != listing.seller {
+            seller_amount = seller_amount.checked_sub(royalty_amount).map_err(StdError::overflow)?;
+            messages.push(BankMsg::Send {
+                to_address: royalty.recipient.to_string(),
+                amount: vec![coin(royalty_amount.u128(), &listing.price.denom)],
+            });
+        }
+    }
+    if !seller_amount.is_zero() {
+        messages.push(BankMsg::Send {
+            to_address: listing.seller.to_string(),
+            amount: vec![coin(seller_amount.u128(), &listing.price.denom)],
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "buy")
+        .add_attribute("token_id", token_id)
+        .add_attribute("price", listing.price.to_string())
+        .add_attribute("seller", listing.seller)
+        .add_attribute("buyer", info.sender))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_start_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    reserve_price: Coin,
+    min_increment: Uint128,
+    end: Expiration,
+    license_duration: Duration,
+) -> Result<Response, ContractError> {
+    // only the owner can auction an exclusive license
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if AUCTIONS.has(deps.storage, &token_id) {
+        return Err(ContractError::AuctionExists {});
+    }
+    assert_not_frozen(deps.storage, &token_id)?;
+    if active_exclusivity(deps.storage, &env.block, &token_id)?.is_some() {
+        return Err(ContractError::ExclusivelyLicensed {});
+    }
+    // the auctioned license is exclusive, other licensees must be gone
+    if has_valid_license(deps.storage, &env.block, &token_id)? {
+        return Err(ContractError::LicensesOutstanding {});
+    }
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    if remaining_licenses(deps.storage, &licensing)? == Some(0) {
+        return Err(ContractError::SoldOut {});
+    }
+    if matches!(end, Expiration::Never {}) || end.is_expired(&env.block) {
+        return Err(ContractError::InvalidAuctionEnd {});
+    }
+
+    let auction = Auction {
+        token_id: token_id.clone(),
+        seller: info.sender.clone(),
+        reserve_price: reserve_price.clone(),
+        min_increment,
+        end,
+        license_duration,
+        highest_bid: None,
+    };
+    AUCTIONS.save(deps.storage, &token_id, &auction)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_auction")
+        .add_attribute("token_id", token_id)
+        .add_attribute("reserve_price", reserve_price.to_string())
+        .add_attribute("end", end.to_string())
+        .add_attribute("seller", info.sender))
+}
+
+pub fn execute_place_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let mut auction = AUCTIONS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoAuction {})?;
+    if auction.end.is_expired(&env.block) {
+        return Err(ContractError::AuctionEnded {});
+    }
+    if auction.seller == info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the first bid must meet the reserve price, later ones must outbid by the minimum increment
+    let min_amount = match &auction.highest_bid {
+        Some(bid) => {
+            bid.amount.amount.checked_add(auction.min_increment).map_err(StdError::overflow)?
+        },
+        None => auction.reserve_price.amount,
+    };
+    let min_bid = coin(min_amount.u128(), &auction.reserve_price.denom);
+    let amount = match &info.funds[..] {
+        [sent] if sent.denom == min_bid.denom && sent.amount >= min_bid.amount => sent.clone(),
+        _ => return Err(ContractError::BidTooLow { min_bid }),
+    };
+
+    // refund the outbid bidder
+    let mut messages = vec![];
+    if let Some(outbid) = auction.highest_bid.take() {
+        messages.push(BankMsg::Send {
+            to_address: outbid.bidder.to_string(),
+            amount: vec![outbid.amount],
+        });
+    }
+    auction.highest_bid = Some(Bid {
+        bidder: info.sender.clone(),
+        amount: amount.clone(),
+    });
+    AUCTIONS.save(deps.storage, &token_id, &auction)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "place_bid")
+        .add_attribute("token_id", token_id)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("bidder", info.sender))
+}
+
+pub fn execute_settle_auction(
+    deps: DepsMut,
+    env: Env,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoAuction {})?;
+    if !auction.end.is_expired(&env.block) {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+    AUCTIONS.remove(deps.storage, &token_id);
+
+    let bid = match auction.highest_bid {
+        Some(bid) => bid,
+        None => {
+            return Ok(Response::new()
+                .add_attribute("action", "settle_auction")
+                .add_attribute("token_id", token_id)
+                .add_attribute("result", "no_bids"))
+        }
+    };
+
+    // the token may have become unlicensable while the auction was running, refund the bidder then
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    let unlicensable = match check_licensable(deps.storage, &env.block, &licensing) {
+        Ok(()) => None,
+        Err(ContractError::SoldOut {}) => Some("sold_out"),
+        Err(ContractError::ExclusivelyLicensed {}) => Some("exclusively_licensed"),
+        Err(ContractError::Disputed {}) => Some("disputed"),
+        Err(ContractError::Suspended {}) => Some("suspended"),
+        Err(err) => return Err(err),
+    };
+    if let Some(result) = unlicensable {
+        let refund_msg = BankMsg::Send {
+            to_address: bid.bidder.to_string(),
+            amount: vec![bid.amount],
+        };
+        return Ok(Response::new()
+            .add_message(refund_msg)
+            .add_attribute("action", "settle_auction")
+            .add_attribute("token_id", token_id)
+            .add_attribute("result", result));
+    }
+    record_license_sale(deps.storage, &licensing)?;
+
+    // grant the exclusive license to the highest bidder
+    let expires = auction.license_duration.after(&env.block);
+    let license = License {
+        token_id: token_id.clone(),
+        licensee: bid.bidder.clone(),
+        payer: bid.bidder.clone(),
+        price: bid.amount.clone(),
+        expires,
+        exclusive: true,
+        granted: false,
+        memo: None,
+        revoked: None,
+        purchased_at: BlockStamp::from(&env.block),
+    };
+    save_new_license(deps.storage, &env.block, &license)?;
+    let exclusivity = Exclusivity {
+        licensee: bid.bidder.clone(),
+        expires,
+        lock_transfer: false,
+    };
+    EXCLUSIVITY.save(deps.storage, &token_id, &exclusivity)?;
+
+    let send_funds_msg = BankMsg::Send {
+        to_address: auction.seller.to_string(),
+        amount: vec![bid.amount.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(send_funds_msg)
+        .add_attribute("action", "settle_auction")
+        .add_attribute("token_id", token_id)
+        .add_attribute("result", "licensed")
+        .add_attribute("price", bid.amount.to_string())
+        .add_attribute("licensee", bid.bidder)
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub fn execute_cancel_auction(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let auction = AUCTIONS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoAuction {})?;
+    if auction.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if auction.highest_bid.is_some() {
+        return Err(ContractError::AuctionHasBids {});
+    }
+    AUCTIONS.remove(deps.storage, &token_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_auction")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_open_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    evidence_uri: String,
+    bond: Coin,
+) -> Result<Response, ContractError> {
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    if token_info.owner == info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if DISPUTES.has(deps.storage, &token_id) {
+        return Err(ContractError::Disputed {});
+    }
+
+    // a dispute nobody can resolve would freeze the token forever
+    let config = CONFIG.load(deps.storage)?;
+    if config.arbitrators.is_empty() {
+        return Err(ContractError::NoArbitrators {});
+    }
+    let sufficient = match &config.min_dispute_bond {
+        Some(min) => bond.denom == min.denom && bond.amount >= min.amount,
+        None => true,
+    };
+    if bond.amount.is_zero() || !sufficient {
+        return Err(ContractError::InvalidBond {});
+    }
+    must_pay(&info.funds, &bond)?;
+
+    let dispute = Dispute {
+        token_id: token_id.clone(),
+        claimant: info.sender.clone(),
+        evidence_uri: evidence_uri.clone(),
+        bond: bond.clone(),
+        opened_at: BlockStamp::from(&env.block),
+    };
+    DISPUTES.save(deps.storage, &token_id, &dispute)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "open_dispute")
+        .add_attribute("token_id", token_id)
+        .add_attribute("evidence_uri", evidence_uri)
+        .add_attribute("bond", bond.to_string())
+        .add_attribute("claimant", info.sender))
+}
+
+pub fn execute_resolve_dispute(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    ruling: DisputeRuling,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.arbitrators.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let dispute = DISPUTES.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoDispute {})?;
+    DISPUTES.remove(deps.storage, &token_id);
+
+    let mut token_info = tokens().load(deps.storage, &token_id)?;
+    let mut messages = vec![];
+    let (result, bond_recipient) = match ruling {
+        DisputeRuling::Claimant => {
+            // hand the token over, sales and auctions set up by the previous owner are void
+            let previous = token_info.owner.clone();
+            token_info.owner = dispute.claimant.clone();
+            token_info.approvals = vec![];
+            tokens().save(deps.storage, &token_id, &token_info)?;
+            LISTINGS.remove(deps.storage, &token_id);
+            record_owner_change(deps.storage, Some(&previous), &token_id)?;
+            if let Some(auction) = AUCTIONS.may_load(deps.storage, &token_id)? {
+                AUCTIONS.remove(deps.storage, &token_id);
+                if let Some(bid) = auction.highest_bid {
+                    messages.push(BankMsg::Send {
+                        to_address: bid.bidder.to_string(),
+                        amount: vec![bid.amount],
+                    });
+                }
+            }
+
+            // the royalty set up by the previous owner is void and the mint deposit changes hands,
+            // the licensing terms follow the token and can be updated by the claimant
+            ROYALTIES.remove(deps.storage, &token_id);
+            if let Some(mut deposit) = DEPOSITS.may_load(deps.storage, &token_id)? {
+                deposit.depositor = dispute.claimant.clone();
+                DEPOSITS.save(deps.storage, &token_id, &deposit)?;
+            }
+
+            // the ISCC claim follows the token, the claimant becomes its registrant
+            iscc_data().update(deps.storage, &token_id, |data| match data {
+                Some(mut data) => {
+                    data.registrant = dispute.claimant.clone();
+                    Ok(data)
+                },
+                None => Err(StdError::not_found("IsccData")),
+            })?;
+            ("claimant", dispute.claimant.clone())
+        },
+        DisputeRuling::Owner => ("owner", token_info.owner),
+    };
+    messages.push(BankMsg::Send {
+        to_address: bond_recipient.to_string(),
+        amount: vec![dispute.bond],
+    });
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "resolve_dispute")
+        .add_attribute("token_id", token_id)
+        .add_attribute("ruling", result)
+        .add_attribute("claimant", dispute.claimant)
+        .add_attribute("arbitrator", info.sender))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_moderate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    hidden: bool,
+    suspended: bool,
+    reason: ReasonCode,
+    forfeit_deposit: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin.as_ref() != Some(&info.sender) && !config.moderators.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // fails for unknown tokens
+    tokens().load(deps.storage, &token_id)?;
+
+    if hidden || suspended {
+        let moderation = Moderation {
+            hidden,
+            suspended,
+            reason,
+            moderator: info.sender.clone(),
+            updated_at: BlockStamp::from(&env.block),
+        };
+        MODERATION.save(deps.storage, &token_id, &moderation)?;
+    } else {
+        MODERATION.remove(deps.storage, &token_id);
+    }
+
+    let id = MODERATION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    MODERATION_COUNT.save(deps.storage, &id)?;
+    let entry = ModerationEntry {
+        id,
+        token_id: token_id.clone(),
+        hidden,
+        suspended,
+        reason,
+        moderator: info.sender.clone(),
+        at: BlockStamp::from(&env.block),
+    };
+    MODERATION_LOG.save(deps.storage, id.into(), &entry)?;
+
+    // forfeited deposits go to the admin, they can't be forfeited once the role was renounced
+    let mut messages = vec![];
+    if forfeit_deposit {
+        let admin = config.admin.ok_or(ContractError::NoForfeitRecipient {})?;
+        let deposit = DEPOSITS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoDeposit {})?;
+        DEPOSITS.remove(deps.storage, &token_id);
+        update_deposits_held(deps.storage, &deposit.amount, false)?;
+        messages.push(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: vec![deposit.amount],
+        });
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "moderate")
+        .add_attribute("token_id", token_id)
+        .add_attribute("hidden", hidden.to_string())
+        .add_attribute("suspended", suspended.to_string())
+        .add_attribute("moderator", info.sender))
+}
+
+pub fn execute_reclaim_deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let deposit = DEPOSITS.may_load(deps.storage, &token_id)?.ok_or(ContractError::NoDeposit {})?;
+    if deposit.depositor != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !deposit.refundable_at.is_expired(&env.block) {
+        return Err(ContractError::DepositLocked {});
+    }
+    // a disputed or suspended token isn't clean, its deposit may still be forfeited
+    assert_not_frozen(deps.storage, &token_id)?;
+
+    DEPOSITS.remove(deps.storage, &token_id);
+    update_deposits_held(deps.storage, &deposit.amount, false)?;
+    let refund_msg = BankMsg::Send {
+        to_address: deposit.depositor.to_string(),
+        amount: vec![deposit.amount.clone()],
+    };
+
+    Ok(Response::new()
+        .add_message(refund_msg)
+        .add_attribute("action", "reclaim_deposit")
+        .add_attribute("token_id", token_id)
+        .add_attribute("amount", deposit.amount.to_string())
+        .add_attribute("depositor", deposit.depositor))
+}
+
+pub fn execute_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let token = tokens().load(deps.storage, &token_id)?;
+    check_can_send(deps.as_ref(), &env, &info, &token)?;
+    // a disputed or suspended token can't shed its deposit this way
+    assert_transferable(deps.storage, &env.block, &token_id)?;
+    // licensees keep referring to the token and its terms
+    let licensed = licenses()
+        .idx
+        .token
+        .prefix(token_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if licensed {
+        return Err(ContractError::LicensesOnRecord {});
+    }
+
+    // free the token id and its ISCC code for new registrations
+    let iscc = iscc_data().load(deps.storage, &token_id)?;
+    tokens().remove(deps.storage, &token_id)?;
+    iscc_data().remove(deps.storage, &token_id)?;
+    ISCC.remove(deps.storage, &iscc.content_id);
+    LICENSING.remove(deps.storage, &token_id);
+    LICENSES_SOLD.remove(deps.storage, &token_id);
+    TOKEN_STATS.remove(deps.storage, &token_id);
+    ROYALTIES.remove(deps.storage, &token_id);
+    LISTINGS.remove(deps.storage, &token_id);
+    MODERATION.remove(deps.storage, &token_id);
+    let count = num_tokens(deps.storage)?.saturating_sub(1);
+    TOKEN_COUNT.save(deps.storage, &count)?;
+    if owned_tokens(deps.storage, &token.owner) == 0 {
+        let mut stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+        stats.owners = stats.owners.saturating_sub(1);
+        STATS.save(deps.storage, &stats)?;
+    }
+
+    // the deposit goes back to whoever paid it, clean period or not
+    let mut res = Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("token_id", token_id.clone())
+        .add_attribute("sender", info.sender);
+    if let Some(deposit) = DEPOSITS.may_load(deps.storage, &token_id)? {
+        DEPOSITS.remove(deps.storage, &token_id);
+        update_deposits_held(deps.storage, &deposit.amount, false)?;
+        res = res
+            .add_message(BankMsg::Send {
+                to_address: deposit.depositor.to_string(),
+                amount: vec![deposit.amount.clone()],
+            })
+            .add_attribute("refund", deposit.amount.to_string());
+    }
+    Ok(res)
+}
+
+pub fn execute_set_mint_deposit(
+    deps: DepsMut,
+    info: MessageInfo,
+    mint_deposit: Option<MintDeposit>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+    config.mint_deposit = mint_deposit;
+    CONFIG.save(deps.storage, &config)?;
+
+    // deposits already held keep the terms they were made under
+    let amount = match &config.mint_deposit {
+        Some(mint_deposit) => mint_deposit.amount.to_string(),
+        None => "none".to_string(),
+    };
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_deposit")
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_set_mint_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    mint_fee: Option<MintFeeMsg>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+    config.mint_fee = mint_fee.map(|fee| validate_mint_fee(deps.api, fee)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let mut res = Response::new().add_attribute("action", "set_mint_fee");
+    res = match &config.mint_fee {
+        Some(mint_fee) => res
+            .add_attribute("amount", mint_fee.amount.to_string())
+            .add_attribute("treasury", mint_fee.treasury.to_string()),
+        None => res.add_attribute("amount", "none"),
+    };
+    Ok(res)
+}
+
+pub fn execute_update_moderators(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+
+    let remove = remove
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<Vec<_>>>()?;
+    config.moderators.retain(|moderator| !remove.contains(moderator));
+    for addr in add {
+        let moderator = deps.api.addr_validate(&addr)?;
+        if !config.moderators.contains(&moderator) {
+            config.moderators.push(moderator);
+        }
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_moderators")
+        .add_attribute("moderators", config.moderators.len().to_string()))
+}
+
+pub fn execute_update_arbitrators(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+
+    // open disputes are left to the remaining arbitrators
+    let remove = remove
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<Vec<_>>>()?;
+    config.arbitrators.retain(|arbitrator| !remove.contains(arbitrator));
+    for addr in add {
+        let arbitrator = deps.api.addr_validate(&addr)?;
+        if !config.arbitrators.contains(&arbitrator) {
+            config.arbitrators.push(arbitrator);
+        }
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_arbitrators")
+        .add_attribute("arbitrators", config.arbitrators.len().to_string()))
+}
+
+pub fn execute_set_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    flags: PauseFlags,
+) -> Result<Response, ContractError> {
+    assert_admin(deps.storage, &info.sender)?;
+    PAUSE.save(deps.storage, &flags)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_pause")
+        .add_attribute("mint", flags.mint.to_string())
+        .add_attribute("license", flags.license.to_string())
+        .add_attribute("transfer", flags.transfer.to_string())
+        .add_attribute("approvals", flags.approvals.to_string()))
+}
+
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: String,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+    let pending_admin = deps.api.addr_validate(&admin)?;
+    config.pending_admin = Some(pending_admin.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("pending_admin", pending_admin)
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.pending_admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::NotPendingAdmin {});
+    }
+    config.admin = config.pending_admin.take();
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_renounce_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = assert_admin(deps.storage, &info.sender)?;
+    config.admin = None;
+    config.pending_admin = None;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("admin", info.sender))
+}
+
+/// Fails unless the sender is the contract admin, returns the config otherwise
+fn assert_admin(storage: &dyn Storage, sender: &Addr) -> Result<Config, ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.admin.as_ref() != Some(sender) {
+        return Err(ContractError::NotAdmin {});
+    }
+    Ok(config)
+}
+
+/// Returns the exclusive license held on the token, if it has not expired yet
+fn active_exclusivity(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    token_id: &str,
+) -> StdResult<Option<Exclusivity>> {
+    let exclusivity = EXCLUSIVITY.may_load(storage, token_id)?;
+    Ok(exclusivity.filter(|exclusivity| !exclusivity.expires.is_expired(block)))
+}
+
+/// License purchase checked against the licensing terms, ready to be recorded
+struct LicensePurchase {
+    licensing: Licensing,
+    licensee: Addr,
+    owner: Addr,
+}
+
+/// Checks that the licensee can buy a license of the token
+fn prepare_license(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    token_id: &str,
+    licensee: &Addr,
+) -> Result<LicensePurchase, ContractError> {
+    let licensing = LICENSING.load(storage, token_id)?;
+    check_licensee(storage, block, token_id, licensee)?;
+    check_licensable(storage, block, &licensing)?;
+
+    let owner = tokens().load(storage, token_id)?.owner;
+    Ok(LicensePurchase {
+        licensing,
+        licensee: licensee.clone(),
+        owner,
+    })
+}
+
+/// Fails if the licensee's previous license of the token prevents a new purchase
+fn check_licensee(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    token_id: &str,
+    licensee: &Addr,
+) -> Result<(), ContractError> {
+    // revoked licensees cannot buy their way back in
+    if let Some(license) = licenses().may_load(storage, (licensee, token_id))? {
+        if license.revoked.is_some() {
+            return Err(ContractError::LicenseRevoked {});
+        }
+        if license.is_valid(block) {
+            return Err(ContractError::AlreadyLicensed {});
+        }
+    }
+    if ESCROWS.has(storage, (licensee, token_id)) {
+        return Err(ContractError::EscrowPending {});
+    }
+    Ok(())
+}
+
+/// Fails if no license of the token can be sold at the moment
+fn check_licensable(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    licensing: &Licensing,
+) -> Result<(), ContractError> {
+    if PAUSE.may_load(storage)?.unwrap_or_default().license {
+        return Err(ContractError::Paused {});
+    }
+    assert_not_frozen(storage, &licensing.token_id)?;
+    // no other licenses can be sold while an exclusive license is active or auctioned
+    if active_exclusivity(storage, block, &licensing.token_id)?.is_some() {
+        return Err(ContractError::ExclusivelyLicensed {});
+    }
+    if AUCTIONS.has(storage, &licensing.token_id) {
+        return Err(ContractError::AuctionExists {});
+    }
+    if licensing.exclusive.is_some() && has_valid_license(storage, block, &licensing.token_id)? {
+        return Err(ContractError::LicensesOutstanding {});
+    }
+    if remaining_licenses(storage, licensing)? == Some(0) {
+        return Err(ContractError::SoldOut {});
+    }
+    Ok(())
+}
+
+/// Returns when a license bought now expires
+fn license_expiration(licensing: &Licensing, block: &BlockInfo) -> Expiration {
+    match &licensing.exclusive {
+        Some(terms) => terms.duration.after(block),
+        None => Expiration::Never {},
+    }
+}
+
+/// Records a license purchase made by the payer. Returns the payment owed to the token owner,
+/// unless there is nothing to pay or it is held in escrow until the refund window closes
+fn record_license(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    purchase: LicensePurchase,
+    payer: &Addr,
+) -> Result<Option<(Addr, Coin)>, ContractError> {
+    let LicensePurchase { licensing, licensee, owner } = purchase;
+    let token_id = licensing.token_id.as_str();
+
+    // count the sale against the license supply
+    record_license_sale(storage, &licensing)?;
+
+    // exclusive terms lock out other licensees until the license expires
+    let expires = license_expiration(&licensing, block);
+    if let Some(terms) = &licensing.exclusive {
+        let exclusivity = Exclusivity {
+            licensee: licensee.clone(),
+            expires,
+            lock_transfer: terms.lock_transfer,
+        };
+        EXCLUSIVITY.save(storage, token_id, &exclusivity)?;
+    }
+
+    // save license transaction
+    let license = License {
+        token_id: token_id.to_string(),
+        licensee: licensee.clone(),
+        payer: payer.clone(),
+        price: licensing.price.clone(),
+        expires,
+        exclusive: licensing.exclusive.is_some(),
+        granted: false,
+        memo: None,
+        revoked: None,
+        purchased_at: BlockStamp::from(block),
+    };
+    save_new_license(storage, block, &license)?;
+
+    if licensing.price.amount.is_zero() {
+        return Ok(None);
+    }
+    match licensing.refund_window {
+        Some(window) => {
+            let escrow = Escrow {
+                token_id: token_id.to_string(),
+                licensee: licensee.clone(),
+                payer: payer.clone(),
+                payee: owner,
+                amount: licensing.price,
+                release_at: window.after(block),
+            };
+            ESCROWS.save(storage, (&licensee, token_id), &escrow)?;
+            Ok(None)
+        },
+        None => Ok(Some((owner, licensing.price))),
+    }
+}
+
+/// Saves a newly issued license and adds it to the registry and token statistics
+fn save_new_license(storage: &mut dyn Storage, block: &BlockInfo, license: &License) -> StdResult<()> {
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    let mut token_stats = TOKEN_STATS.may_load(storage, &license.token_id)?.unwrap_or_default();
+
+    let first_license = licenses()
+        .prefix(&license.licensee)
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if first_license {
+        stats.licensees += 1;
+    }
+    stats.licenses_sold += 1;
+    // granted licenses are paid outside of the contract, if at all
+    if !license.granted {
+        add_coin(&mut stats.revenue, &license.price);
+        add_coin(&mut token_stats.revenue, &license.price);
+    }
+    token_stats.last_license_height = Some(block.height);
+
+    licenses().save(storage, (&license.licensee, &license.token_id), license)?;
+    STATS.save(storage, &stats)?;
+    TOKEN_STATS.save(storage, &license.token_id, &token_stats)
+}
+
+/// Takes a refunded license back out of the stats and gives it back to the supply, call it once
+/// the license is updated or removed
+fn save_refunded_license(storage: &mut dyn Storage, license: &License) -> StdResult<()> {
+    release_license_sale(storage, &license.token_id)?;
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    let mut token_stats = TOKEN_STATS.may_load(storage, &license.token_id)?.unwrap_or_default();
+
+    let last_license = licenses()
+        .prefix(&license.licensee)
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if last_license {
+        stats.licensees = stats.licensees.saturating_sub(1);
+    }
+    stats.licenses_sold = stats.licenses_sold.saturating_sub(1);
+    if !license.granted {
+        sub_coin(&mut stats.revenue, &license.price)?;
+        sub_coin(&mut token_stats.revenue, &license.price)?;
+    }
+
+    STATS.save(storage, &stats)?;
+    TOKEN_STATS.save(storage, &license.token_id, &token_stats)
+}
+
+/// Updates the number of distinct owners after a token was minted or moved away from `previous`
+fn record_owner_change(storage: &mut dyn Storage, previous: Option<&Addr>, token_id: &str) -> StdResult<()> {
+    let owner = tokens().load(storage, token_id)?.owner;
+    if previous == Some(&owner) {
+        return Ok(());
+    }
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    if owned_tokens(storage, &owner) == 1 {
+        stats.owners += 1;
+    }
+    if let Some(previous) = previous {
+        if owned_tokens(storage, previous) == 0 {
+            stats.owners = stats.owners.saturating_sub(1);
+        }
+    }
+    STATS.save(storage, &stats)
+}
+
+/// Counts the tokens owned by an address, stopping at two as only 0, 1 or more matters
+fn owned_tokens(storage: &dyn Storage, owner: &Addr) -> usize {
+    tokens()
+        .idx
+        .owner
+        .prefix(owner.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .take(2)
+        .count()
+}
+
+/// Whether any license of the token is still valid, which rules out selling exclusive ones
+fn has_valid_license(storage: &dyn Storage, block: &BlockInfo, token_id: &str) -> StdResult<bool> {
+    let token_licenses = licenses().idx.token.prefix(token_id.to_string());
+    for item in token_licenses.range(storage, None, None, Order::Ascending) {
+        let (_, license) = item?;
+        if license.is_valid(block) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns how many licenses can still be sold, `None` if the supply is unlimited
+fn remaining_licenses(storage: &dyn Storage, licensing: &Licensing) -> StdResult<Option<u64>> {
+    let sold = LICENSES_SOLD.may_load(storage, &licensing.token_id)?.unwrap_or_default();
+    Ok(licensing.max_licenses.map(|max| max.saturating_sub(sold)))
+}
 
-    // store iscc data related to content id 
-    let iscc_data = IsccData {
-        token_id: msg.token_id.clone(),
-        meta_id: msg.meta_id.clone(),
-        content_id: msg.content_id.clone(),
-        data_id: msg.data_id.clone(),
-        instance_id: msg.instance_id.clone(),
-        tophash: msg.tophash.clone(),
-    };
-    ISCC_DATA.save(deps.storage, &msg.content_id, &iscc_data)?;
+/// Gives back a sold license to the supply after a refund
+fn release_license_sale(storage: &mut dyn Storage, token_id: &str) -> StdResult<u64> {
+    LICENSES_SOLD.update(storage, token_id, |sold| -> StdResult<_> {
+        Ok(sold.unwrap_or_default().saturating_sub(1))
+    })
+}
 
-    // associate iscc content id with token
-    ISCC.update(
-        deps.storage, 
-        &msg.content_id, 
-        | old | match old {
-            Some(_) => Err(ContractError::Claimed {}),
-            None => Ok(msg.token_id.clone())
+/// Increments the licenses sold, failing if the supply is exhausted
+fn record_license_sale(storage: &mut dyn Storage, licensing: &Licensing) -> Result<u64, ContractError> {
+    LICENSES_SOLD.update(storage, &licensing.token_id, |sold| {
+        let sold = sold.unwrap_or_default() + 1;
+        match licensing.max_licenses {
+            Some(max) if sold > max => Err(ContractError::SoldOut {}),
+            _ => Ok(sold),
         }
-    )?;
+    })
+}
 
-    // store licensing data
-    let licensing = Licensing {
-        token_id: msg.token_id.clone(),
-        url: msg.license_url.clone(),
-        price: msg.license_price,
-    };
-    LICENSING.save(deps.storage, &msg.token_id, &licensing)?;
+/// Fails while licensing is frozen by an open dispute or a suspension
+fn assert_not_frozen(storage: &dyn Storage, token_id: &str) -> Result<(), ContractError> {
+    if DISPUTES.has(storage, token_id) {
+        return Err(ContractError::Disputed {});
+    }
+    assert_not_suspended(storage, token_id)
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("token_id", msg.token_id)
-        .add_attribute("name", msg.name)
-        .add_attribute("content_id", msg.content_id)
-        .add_attribute("owner", msg.owner)
-    )
+fn assert_not_suspended(storage: &dyn Storage, token_id: &str) -> Result<(), ContractError> {
+    match MODERATION.may_load(storage, token_id)? {
+        Some(moderation) if moderation.suspended => Err(ContractError::Suspended {}),
+        _ => Ok(()),
+    }
 }
 
-pub fn execute_licensing(
-    deps: DepsMut,
-    info: MessageInfo,
-    token_id: String,
-) -> Result<Response, ContractError> {
-    // load licensing info 
-    let licensing = LICENSING.load(deps.storage, &token_id)?;
+/// Fails if the token is disputed or suspended, its license is being auctioned, or an active
+/// exclusive license locks its ownership
+fn assert_transferable(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    assert_not_frozen(storage, token_id)?;
+    // the seller collects the winning bid, so the token can't change hands meanwhile
+    if AUCTIONS.has(storage, token_id) {
+        return Err(ContractError::AuctionExists {});
+    }
+    match active_exclusivity(storage, block, token_id)? {
+        Some(exclusivity) if exclusivity.lock_transfer => Err(ContractError::TransferLocked {}),
+        _ => Ok(()),
+    }
+}
 
-    // extract coins sent
-    // TODO handle multiple coins
-    let coins_sent = &info.funds[0];
-    
-    // check if the coins sent are the ones accepted for licensing and if the amount sent is enough
-    if coins_sent.denom != licensing.price.denom ||  coins_sent.amount < licensing.price.amount {
-        return Err(ContractError::Unauthorized{}); // TODO throw custom error
+/// Fails unless the sender owns the token or is an approved operator
+fn check_can_send(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token: &TokenInfo,
+) -> Result<(), ContractError> {
+    // owner can send
+    if token.owner == info.sender {
+        return Ok(());
     }
 
-    // load token info and send funds to token owner
-    let token_info = tokens().load(deps.storage, &token_id)?;
-    let send_funds_msg = BankMsg::Send {
-        to_address: token_info.owner.to_string(),
-        amount: vec![licensing.price],
-    };
+    // any non-expired token approval can send
+    if token
+        .approvals
+        .iter()
+        .any(|apr| apr.spender == info.sender && !apr.is_expired(&env.block))
+    {
+        return Ok(());
+    }
 
-    // save license \transaction
-    let license = License {
-        token_id: token_id.clone(),
-        price: coins_sent.clone(),
-        licensee: info.sender.clone(),
-    };
-    LICENSE.save(deps.storage, (&info.sender, &token_id), &license)?;
+    // operator can send
+    let op = OPERATORS.may_load(deps.storage, (&token.owner, &info.sender))?;
+    match op {
+        Some(ex) if !ex.is_expired(&env.block) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
 
-    Ok(Response::new()
-        .add_message(send_funds_msg)
-        .add_attribute("action", "license")
-        .add_attribute("token_id", token_id)
-        .add_attribute("price", info.funds[0].amount)
-        .add_attribute("licensee", info.sender))
+/// Checks that exactly the expected coin, and nothing else, was sent
+fn must_pay(funds: &[Coin], expected: &Coin) -> Result<(), ContractError> {
+    must_pay_all(funds, std::slice::from_ref(expected))
+}
+
+/// Checks that the funds sent match the expected coins, denom by denom
+fn must_pay_all(funds: &[Coin], expected: &[Coin]) -> Result<(), ContractError> {
+    let mut sent = vec![];
+    funds.iter().for_each(|coin| add_coin(&mut sent, coin));
+    let mut required = vec![];
+    expected.iter().for_each(|coin| add_coin(&mut required, coin));
+
+    if sent != required {
+        let expected = if required.is_empty() {
+            "no funds".to_string()
+        } else {
+            required.iter().map(Coin::to_string).collect::<Vec<_>>().join(",")
+        };
+        return Err(ContractError::InvalidFunds { expected });
+    }
+    Ok(())
+}
+
+/// Adds the coin to a list of coins kept sorted by denom, skipping zero amounts
+fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) {
+    if coin.amount.is_zero() {
+        return;
+    }
+    match coins.binary_search_by(|c| c.denom.cmp(&coin.denom)) {
+        Ok(index) => coins[index].amount += coin.amount,
+        Err(index) => coins.insert(index, coin.clone()),
+    }
+}
+
+fn sub_coin(coins: &mut Vec<Coin>, coin: &Coin) -> StdResult<()> {
+    if let Ok(index) = coins.binary_search_by(|c| c.denom.cmp(&coin.denom)) {
+        coins[index].amount = coins[index].amount.checked_sub(coin.amount).map_err(StdError::overflow)?;
+        if coins[index].amount.is_zero() {
+            coins.remove(index);
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::ContractInfo {} => to_binary(&query_contract_info(deps)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::NftInfo { 
             token_id 
         } => to_binary(&query_nft_info(
@@ -223,71 +2087,364 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             owner,
             include_expired,
             start_after,
+            end_before,
             limit,
+            order,
         } => to_binary(&query_all_approvals(
             deps,
             env,
             owner,
             include_expired.unwrap_or(false),
-            start_after,
-            limit,
+            Pagination { start_after, end_before, limit, order },
         )?),
         QueryMsg::NumTokens {} => to_binary(&query_num_tokens(deps)?),
         QueryMsg::Tokens {
             owner,
+            include_hidden,
             start_after,
+            end_before,
             limit,
+            order,
         } => to_binary(&query_tokens(
             deps, 
             owner, 
-            start_after, 
-            limit
+            include_hidden.unwrap_or(false),
+            Pagination { start_after, end_before, limit, order },
         )?),
         QueryMsg::AllTokens { 
+            include_hidden,
             start_after, 
-            limit 
-        } => {
-            to_binary(&query_all_tokens(deps, start_after, limit)?)
-        },
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_all_tokens(
+            deps,
+            include_hidden.unwrap_or(false),
+            Pagination { start_after, end_before, limit, order },
+        )?),
         QueryMsg::GetByContentId {
-            content_id
+            content_id,
+            include_expired,
         } => {
-            to_binary(&get_by_iscc_code(deps, content_id)?)
-        }
+            to_binary(&get_by_iscc_code(deps, env, content_id, include_expired.unwrap_or(false))?)
+        },
+        QueryMsg::EarliestRegistration {
+            unit,
+            max_distance,
+            start_after,
+            limit,
+        } => to_binary(&query_earliest_registration(
+            deps,
+            unit,
+            max_distance.unwrap_or(0),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Token {
+            token_id,
+            include_expired,
+        } => to_binary(&query_token(deps, &env, token_id, include_expired.unwrap_or(false))?),
+        QueryMsg::TokensInfo {
+            owner,
+            include_expired,
+            include_hidden,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_tokens_info(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            include_hidden.unwrap_or(false),
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::AllTokensInfo {
+            include_expired,
+            include_hidden,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_all_tokens_info(
+            deps,
+            env,
+            include_expired.unwrap_or(false),
+            include_hidden.unwrap_or(false),
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::Licenses {
+            licensee,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_licenses(
+            deps,
+            licensee,
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::TokenLicenses {
+            token_id,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_token_licenses(
+            deps,
+            token_id,
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::Stats {} => to_binary(&query_stats(deps)?),
+        QueryMsg::Dispute {
+            token_id,
+        } => to_binary(&DISPUTES.may_load(deps.storage, &token_id)?),
+        QueryMsg::TokenStats {
+            token_id,
+        } => to_binary(&query_token_stats(deps, token_id)?),
+        QueryMsg::Listings {
+            include_hidden,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_listings(
+            deps,
+            include_hidden.unwrap_or(false),
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::Deposit {
+            token_id,
+        } => to_binary(&DEPOSITS.may_load(deps.storage, &token_id)?),
+        QueryMsg::DepositHoldings {} => to_binary(&query_deposit_holdings(deps)?),
+        QueryMsg::Admin {} => to_binary(&query_admin(deps)?),
+        QueryMsg::PauseStatus {} => to_binary(&PAUSE.may_load(deps.storage)?.unwrap_or_default()),
+        QueryMsg::Moderation {
+            token_id,
+        } => to_binary(&MODERATION.may_load(deps.storage, &token_id)?),
+        QueryMsg::ModerationLog {
+            token_id,
+            start_after,
+            end_before,
+            limit,
+            order,
+        } => to_binary(&query_moderation_log(
+            deps,
+            token_id,
+            Pagination { start_after, end_before, limit, order },
+        )?),
+        QueryMsg::Auction {
+            token_id,
+        } => to_binary(&query_auction(deps, token_id)?),
+        QueryMsg::Exclusivity {
+            token_id,
+        } => to_binary(&active_exclusivity(deps.storage, &env.block, &token_id)?),
+        QueryMsg::IsLicensed {
+            token_id,
+            licensee,
+        } => to_binary(&query_is_licensed(deps, env, token_id, licensee)?),
+        QueryMsg::Escrow {
+            token_id,
+            licensee,
+        } => to_binary(&query_escrow(deps, token_id, licensee)?),
+        QueryMsg::QuoteLicense {
+            token_id,
+            licensee,
+        } => to_binary(&query_quote_license(deps, env, token_id, licensee)?),
+        QueryMsg::LicenseSupply {
+            token_id,
+        } => to_binary(&query_license_supply(deps, token_id)?),
     }
 }
 
-fn get_by_iscc_code(deps: Deps, content_id: String) -> StdResult<Option<TokenResponse>> {
-    let token_id_result = ISCC.load(deps.storage, &content_id);
-
-    match token_id_result {
-        Ok(token_id) => {
-            let token_info = tokens().load(deps.storage, &token_id)?;
-            let iscc_data = ISCC_DATA.load(deps.storage, &content_id)?;
-            let licensing = LICENSING.load(deps.storage, &token_id)?;
-            
-            Ok(Some(TokenResponse {
-                token_id: token_id,
-                owner: token_info.owner,
-                name: token_info.name,
-                description: Some(token_info.description),
-                image: token_info.image,
-                meta_id: iscc_data.meta_id,
-                content_id: iscc_data.content_id,
-                data_id: iscc_data.data_id,
-                instance_id: iscc_data.instance_id,
-                license_url: licensing.url,
-                license_price: licensing.price,
-            }))
-        },
-        Err(_) => Ok(None)
+fn get_by_iscc_code(
+    deps: Deps,
+    env: Env,
+    content_id: String,
+    include_expired: bool,
+) -> StdResult<Option<TokenResponse>> {
+    // only an unknown content id resolves to nothing, any other failure is reported
+    match ISCC.may_load(deps.storage, &content_id)? {
+        Some(token_id) => Ok(Some(query_token(deps, &env, token_id, include_expired)?)),
+        None => Ok(None),
+    }
+}
+
+fn query_earliest_registration(
+    deps: Deps,
+    unit: IsccUnit,
+    max_distance: u32,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<EarliestRegistrationResponse> {
+    let unit_code = |data: &IsccData| match &unit {
+        IsccUnit::ContentId(_) => data.content_id.clone(),
+        IsccUnit::DataId(_) => data.data_id.clone(),
+        IsccUnit::InstanceId(_) => data.instance_id.clone(),
+    };
+    let code = match &unit {
+        IsccUnit::ContentId(code) | IsccUnit::DataId(code) | IsccUnit::InstanceId(code) => code,
+    };
+
+    // exact matches are resolved through the iscc maps
+    let mut next_start_after = None;
+    let candidates: Vec<(IsccData, u32)> = if max_distance == 0 {
+        let token_ids: Vec<String> = match &unit {
+            IsccUnit::ContentId(code) => ISCC.may_load(deps.storage, code)?.into_iter().collect(),
+            IsccUnit::DataId(code) => index_token_ids(
+                iscc_data().idx.data.prefix(code.clone()).keys(deps.storage, None, None, Order::Ascending),
+            )?,
+            IsccUnit::InstanceId(code) => index_token_ids(
+                iscc_data().idx.instance.prefix(code.clone()).keys(deps.storage, None, None, Order::Ascending),
+            )?,
+        };
+        token_ids
+            .iter()
+            .map(|token_id| Ok((iscc_data().load(deps.storage, token_id)?, 0)))
+            .collect::<StdResult<_>>()?
+    } else {
+        // similar codes can only be found by comparing against every registration, one page of
+        // registrations at a time
+        let target = IsccCode::decode(code)
+            .ok_or_else(|| StdError::generic_err(format!("Invalid ISCC code: {}", code)))?;
+        let limit = limit.unwrap_or(DEFAULT_SCAN_LIMIT).min(MAX_SCAN_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let mut candidates = vec![];
+        let mut scanned: Option<String> = None;
+        let registrations = iscc_data().range(deps.storage, start, None, Order::Ascending);
+        for (index, item) in registrations.enumerate() {
+            let (_, data) = item?;
+            if index == limit {
+                next_start_after = scanned;
+                break;
+            }
+            scanned = Some(data.token_id.clone());
+            let distance = IsccCode::decode(&unit_code(&data))
+                .and_then(|other| target.distance(&other));
+            if let Some(distance) = distance.filter(|d| *d <= max_distance) {
+                candidates.push((data, distance));
+            }
+        }
+        candidates
+    };
+
+    // ties within the same block are resolved by the closest code
+    let earliest = candidates
+        .into_iter()
+        .min_by_key(|(data, distance)| (data.registered_at.height, *distance));
+    Ok(EarliestRegistrationResponse {
+        registration: earliest.map(|(data, distance)| RegistrationMatch {
+            code: unit_code(&data),
+            token_id: data.token_id,
+            registrant: data.registrant,
+            registered_at: data.registered_at,
+            distance,
+        }),
+        next_start_after,
+    })
+}
+
+/// Collects the token ids from the keys of an index over token ids
+fn index_token_ids(keys: impl Iterator<Item = Vec<u8>>) -> StdResult<Vec<String>> {
+    keys.map(|key| String::from_utf8(key).map_err(StdError::from)).collect()
+}
+
+fn query_token(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<TokenResponse> {
+    let token_info = tokens().load(deps.storage, &token_id)?;
+    let iscc_data = iscc_data().load(deps.storage, &token_id)?;
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    let licenses_sold = LICENSES_SOLD.may_load(deps.storage, &token_id)?.unwrap_or_default();
+    let licenses_remaining = remaining_licenses(deps.storage, &licensing)?;
+    let licensing_status = licensing_status(deps.storage, &env.block, &licensing)?;
+    let approvals = humanize_approvals(&env.block, &token_info, include_expired);
+
+    Ok(TokenResponse {
+        token_id,
+        owner: token_info.owner,
+        name: token_info.name,
+        description: Some(token_info.description),
+        image: token_info.image,
+        meta_id: iscc_data.meta_id,
+        content_id: iscc_data.content_id,
+        data_id: iscc_data.data_id,
+        instance_id: iscc_data.instance_id,
+        tophash: iscc_data.tophash,
+        registered_at: iscc_data.registered_at,
+        authorship: iscc_data.authorship,
+        approvals,
+        license_url: licensing.url,
+        license_price: licensing.price,
+        licensing_status,
+        licenses_sold,
+        licenses_remaining,
+    })
+}
+
+/// Maps the result of the licensing checks to a status
+fn licensing_status(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    licensing: &Licensing,
+) -> StdResult<LicensingStatus> {
+    match check_licensable(storage, block, licensing) {
+        Ok(()) => Ok(LicensingStatus::Available),
+        Err(ContractError::ExclusivelyLicensed {}) => Ok(LicensingStatus::Exclusive),
+        Err(ContractError::LicensesOutstanding {}) => Ok(LicensingStatus::LicensesOutstanding),
+        Err(ContractError::SoldOut {}) => Ok(LicensingStatus::SoldOut),
+        Err(ContractError::AuctionExists {}) => Ok(LicensingStatus::Auctioned),
+        Err(ContractError::Paused {}) => Ok(LicensingStatus::Paused),
+        Err(ContractError::Disputed {}) => Ok(LicensingStatus::Disputed),
+        Err(ContractError::Suspended {}) => Ok(LicensingStatus::Suspended),
+        Err(ContractError::Std(err)) => Err(err),
+        Err(err) => Err(StdError::generic_err(err.to_string())),
     }
 }
 
+fn query_tokens_info(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: bool,
+    include_hidden: bool,
+    pagination: Pagination,
+) -> StdResult<TokensInfoResponse> {
+    let token_ids = query_tokens(deps, owner, include_hidden, pagination)?.tokens;
+    let tokens: StdResult<Vec<_>> = token_ids
+        .into_iter()
+        .map(|token_id| query_token(deps, &env, token_id, include_expired))
+        .collect();
+    Ok(TokensInfoResponse { tokens: tokens? })
+}
+
+fn query_all_tokens_info(
+    deps: Deps,
+    env: Env,
+    include_expired: bool,
+    include_hidden: bool,
+    pagination: Pagination,
+) -> StdResult<TokensInfoResponse> {
+    let token_ids = query_all_tokens(deps, include_hidden, pagination)?.tokens;
+    let tokens: StdResult<Vec<_>> = token_ids
+        .into_iter()
+        .map(|token_id| query_token(deps, &env, token_id, include_expired))
+        .collect();
+    Ok(TokensInfoResponse { tokens: tokens? })
+}
+
 fn query_contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
     CONTRACT_INFO.load(deps.storage)
 }
 
+fn query_config(deps: Deps) -> StdResult<Config> {
+    CONFIG.load(deps.storage)
+}
+
 fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
     let info = tokens().load(deps.storage, &token_id)?;
     Ok(NftInfoResponse {
@@ -335,17 +2492,15 @@ fn query_all_approvals(
     env: Env,
     owner: String,
     include_expired: bool,
-    start_after: Option<String>,
-    limit: Option<u32>,
+    pagination: Pagination,
 ) -> StdResult<ApprovedForAllResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start_addr = maybe_addr(deps.api, start_after)?;
-    let start = start_addr.map(|addr| Bound::exclusive(addr.as_ref()));
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(raw_key)?;
 
     let owner_addr = deps.api.addr_validate(&owner)?;
     let res: StdResult<Vec<_>> = OPERATORS
         .prefix(&owner_addr)
-        .range(deps.storage, start, None, Order::Ascending)
+        .range(deps.storage, min, max, order)
         .filter(|r| include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block))
         .take(limit)
         .map(parse_approval)
@@ -387,41 +2542,238 @@ fn query_num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
 fn query_tokens(
     deps: Deps,
     owner: String,
-    start_after: Option<String>,
-    limit: Option<u32>,
+    include_hidden: bool,
+    pagination: Pagination,
 ) -> StdResult<TokensResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(Bound::exclusive);
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(raw_key)?;
 
     let owner_addr = deps.api.addr_validate(&owner)?;
-    let pks: Vec<_> = tokens()
+    let res: Result<Vec<_>, _> = tokens()
         .idx
         .owner
         .prefix(owner_addr)
-        .keys(deps.storage, start, None, Order::Ascending)
+        .keys(deps.storage, min, max, order)
+        .map(String::from_utf8)
+        .filter(|token_id| match token_id {
+            Ok(token_id) => include_hidden || !is_hidden(deps.storage, token_id),
+            Err(_) => true,
+        })
         .take(limit)
         .collect();
-
-    let res: Result<Vec<_>, _> = pks.iter().map(|v| String::from_utf8(v.to_vec())).collect();
     let tokens = res.map_err(StdError::invalid_utf8)?;
     Ok(TokensResponse { tokens })
 }
 
 fn query_all_tokens(
     deps: Deps,
-    start_after: Option<String>,
-    limit: Option<u32>,
+    include_hidden: bool,
+    pagination: Pagination,
 ) -> StdResult<TokensResponse> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start_addr = maybe_addr(deps.api, start_after)?;
-    let start = start_addr.map(|addr| Bound::exclusive(addr.as_ref()));
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(raw_key)?;
 
     let tokens: StdResult<Vec<String>> = tokens()
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
+        .range(deps.storage, min, max, order)
         .map(|item| item.map(|(k, _)| String::from_utf8_lossy(&k).to_string()))
+        .filter(|token_id| match token_id {
+            Ok(token_id) => include_hidden || !is_hidden(deps.storage, token_id),
+            Err(_) => true,
+        })
+        .take(limit)
         .collect();
     Ok(TokensResponse { tokens: tokens? })
 }
 
-// TODO implement tests
\ No newline at end of file
+fn query_licenses(
+    deps: Deps,
+    licensee: String,
+    pagination: Pagination,
+) -> StdResult<LicensesResponse> {
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(raw_key)?;
+
+    let licensee = deps.api.addr_validate(&licensee)?;
+    let licenses: StdResult<Vec<License>> = licenses()
+        .prefix(&licensee)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| item.map(|(_, license)| license))
+        .collect();
+    Ok(LicensesResponse { licenses: licenses? })
+}
+
+fn query_token_licenses(
+    deps: Deps,
+    token_id: String,
+    pagination: Pagination,
+) -> StdResult<LicensesResponse> {
+    let limit = pagination.limit();
+    // the index is ordered by primary key, made of the length prefixed licensee and the token id
+    let (min, max, order) = pagination.range(|licensee| {
+        Ok((&Addr::unchecked(licensee), token_id.as_str()).joined_key())
+    })?;
+
+    let licenses: StdResult<Vec<License>> = licenses()
+        .idx
+        .token
+        .prefix(token_id.clone())
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| item.map(|(_, license)| license))
+        .collect();
+    Ok(LicensesResponse { licenses: licenses? })
+}
+
+fn query_listings(
+    deps: Deps,
+    include_hidden: bool,
+    pagination: Pagination,
+) -> StdResult<ListingsResponse> {
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(raw_key)?;
+
+    let listings: StdResult<Vec<Listing>> = LISTINGS
+        .range(deps.storage, min, max, order)
+        .map(|item| item.map(|(_, listing)| listing))
+        .filter(|listing| match listing {
+            Ok(listing) => include_hidden || !is_hidden(deps.storage, &listing.token_id),
+            Err(_) => true,
+        })
+        .take(limit)
+        .collect();
+    Ok(ListingsResponse { listings: listings? })
+}
+
+fn query_moderation_log(
+    deps: Deps,
+    token_id: Option<String>,
+    pagination: Pagination<u64>,
+) -> StdResult<ModerationLogResponse> {
+    let limit = pagination.limit();
+    let (min, max, order) = pagination.range(id_key)?;
+
+    let entries: StdResult<Vec<ModerationEntry>> = MODERATION_LOG
+        .range(deps.storage, min, max, order)
+        .map(|item| item.map(|(_, entry)| entry))
+        .filter(|entry| match (entry, &token_id) {
+            (Ok(entry), Some(token_id)) => &entry.token_id == token_id,
+            _ => true,
+        })
+        .take(limit)
+        .collect();
+    Ok(ModerationLogResponse { entries: entries? })
+}
+
+/// Whether a moderator hid the token from list queries
+fn is_hidden(storage: &dyn Storage, token_id: &str) -> bool {
+    matches!(MODERATION.may_load(storage, token_id), Ok(Some(moderation)) if moderation.hidden)
+}
+
+fn query_deposit_holdings(deps: Deps) -> StdResult<DepositHoldingsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(DepositHoldingsResponse {
+        mint_deposit: config.mint_deposit,
+        held: DEPOSITS_HELD.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+fn query_admin(deps: Deps) -> StdResult<AdminResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(AdminResponse {
+        admin: config.admin,
+        pending_admin: config.pending_admin,
+    })
+}
+
+fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(StatsResponse {
+        total_tokens: num_tokens(deps.storage)?,
+        licenses_sold: stats.licenses_sold,
+        revenue: stats.revenue,
+        owners: stats.owners,
+        licensees: stats.licensees,
+    })
+}
+
+fn query_token_stats(deps: Deps, token_id: String) -> StdResult<TokenStatsResponse> {
+    // fails for unknown tokens
+    LICENSING.load(deps.storage, &token_id)?;
+    let token_stats = TOKEN_STATS.may_load(deps.storage, &token_id)?.unwrap_or_default();
+    let licenses_sold = LICENSES_SOLD.may_load(deps.storage, &token_id)?.unwrap_or_default();
+    Ok(TokenStatsResponse {
+        token_id,
+        licenses_sold,
+        revenue: token_stats.revenue,
+        last_license_height: token_stats.last_license_height,
+    })
+}
+
+fn query_auction(deps: Deps, token_id: String) -> StdResult<Auction> {
+    AUCTIONS.load(deps.storage, &token_id)
+}
+
+fn query_is_licensed(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    licensee: String,
+) -> StdResult<IsLicensedResponse> {
+    let licensee = deps.api.addr_validate(&licensee)?;
+    let license = licenses().may_load(deps.storage, (&licensee, &token_id))?;
+    Ok(IsLicensedResponse {
+        licensed: matches!(license, Some(license) if license.is_valid(&env.block)),
+    })
+}
+
+fn query_escrow(deps: Deps, token_id: String, licensee: String) -> StdResult<Option<Escrow>> {
+    let licensee = deps.api.addr_validate(&licensee)?;
+    ESCROWS.may_load(deps.storage, (&licensee, &token_id))
+}
+
+fn query_quote_license(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    licensee: Option<String>,
+) -> StdResult<QuoteResponse> {
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    let owner = tokens().load(deps.storage, &token_id)?.owner;
+
+    // run the same checks as a purchase, reporting the failure instead of returning it
+    let licensee = maybe_addr(deps.api, licensee)?;
+    let checked = match &licensee {
+        Some(licensee) => prepare_license(deps.storage, &env.block, &token_id, licensee).map(|_| ()),
+        None => check_licensable(deps.storage, &env.block, &licensing),
+    };
+
+    let mut funds = vec![];
+    add_coin(&mut funds, &licensing.price);
+    let payouts = funds
+        .iter()
+        .map(|amount| Payout {
+            recipient: owner.clone(),
+            amount: amount.clone(),
+        })
+        .collect();
+
+    Ok(QuoteResponse {
+        funds,
+        payouts,
+        escrow_until: licensing.refund_window.map(|window| window.after(&env.block)),
+        expires: license_expiration(&licensing, &env.block),
+        exclusive: licensing.exclusive.is_some(),
+        error: checked.err().map(|err| err.to_string()),
+    })
+}
+
+fn query_license_supply(deps: Deps, token_id: String) -> StdResult<LicenseSupplyResponse> {
+    let licensing = LICENSING.load(deps.storage, &token_id)?;
+    let sold = LICENSES_SOLD.may_load(deps.storage, &token_id)?.unwrap_or_default();
+    Ok(LicenseSupplyResponse {
+        max_licenses: licensing.max_licenses,
+        sold,
+        remaining: remaining_licenses(deps.storage, &licensing)?,
+    })
+}
@@ -0,0 +1,2426 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coin, from_binary, to_vec, Addr, Api, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal,
+    DepsMut, Empty, Env, OwnedDeps, RecoverPubkeyError, Response, StdResult, Uint128,
+    VerificationError,
+};
+use cw0::{Duration, Expiration};
+use cw721::{ApprovedForAllResponse, TokensResponse};
+use cw721_base::state::{num_tokens, tokens};
+use k256::ecdsa::signature::DigestSigner;
+use k256::ecdsa::{Signature, SigningKey};
+use k256::EncodedPoint;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use crate::contract::{execute, instantiate, query};
+use crate::crypto::secp256k1_address;
+use crate::msg::{
+    AdminResponse, DisputeRuling, EarliestRegistrationResponse, ExecuteMsg, InstantiateMsg,
+    IsccUnit, LicenseItem, LicensesResponse, LicensingStatus, ListingsResponse, MintFeeMsg,
+    MintMsg, MintVoucher, ModerationLogResponse, OrderBy, Payout, QueryMsg, QuoteResponse,
+    RoyaltyMsg, StatsResponse, TokenResponse, TokenStatsResponse,
+};
+use crate::state::{
+    iscc_data, licenses, Authorship, ExclusiveTerms, MintDeposit, PauseFlags, ReasonCode,
+    SignatureScheme, CONFIG, DEPOSITS, DEPOSITS_HELD, DISPUTES, ESCROWS, EXCLUSIVITY,
+    LICENSES_SOLD, LICENSING, LISTINGS, ROYALTIES, STATS,
+};
+use crate::ContractError;
+
+const CONTENT_ID: &str = "3CC23456789rB";
+/// Content id a single bit away from `CONTENT_ID`
+const SIMILAR_CONTENT_ID: &str = "3CC23456789rC";
+
+fn mint_msg(token_id: &str, content_id: &str) -> MintMsg {
+    MintMsg {
+        token_id: token_id.to_string(),
+        owner: "alice".to_string(),
+        name: "name".to_string(),
+        description: "description".to_string(),
+        image: "image".to_string(),
+        meta_id: "meta".to_string(),
+        content_id: content_id.to_string(),
+        data_id: format!("data-{}", token_id),
+        instance_id: format!("instance-{}", token_id),
+        tophash: "tophash".to_string(),
+        license_url: "https://license".to_string(),
+        license_price: coin(10, "uatom"),
+        license_exclusive: None,
+        max_licenses: None,
+        refund_window: None,
+        royalty: Some(RoyaltyMsg {
+            recipient: "creator".to_string(),
+            share: Decimal::percent(10),
+        }),
+        authorship: None,
+    }
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Licium".to_string(),
+        symbol: "LIC".to_string(),
+        admin: None,
+        moderators: None,
+        max_mint_batch: None,
+        arbitrators: Some(vec!["judge".to_string()]),
+        min_dispute_bond: Some(coin(5, "uatom")),
+        mint_deposit: None,
+        mint_fee: None,
+    }
+}
+
+/// Funds to send with a single mint
+fn mint_funds(msg: &InstantiateMsg) -> Vec<Coin> {
+    let deposit = msg
+        .mint_deposit
+        .as_ref()
+        .map(|deposit| deposit.amount.clone());
+    let fee = msg.mint_fee.as_ref().map(|fee| fee.amount.clone());
+    deposit.into_iter().chain(fee).collect()
+}
+
+/// Instantiates the contract with alice as admin and mints token "t1" owned by alice
+fn setup(msg: InstantiateMsg) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    let funds = mint_funds(&msg);
+    instantiate(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    let mint = ExecuteMsg::Mint(mint_msg("t1", CONTENT_ID));
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &funds), mint).unwrap();
+    deps
+}
+
+fn bank_sends(res: &Response) -> Vec<(String, Vec<Coin>)> {
+    res.messages
+        .iter()
+        .filter_map(|msg| match &msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                Some((to_address.clone(), amount.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn attribute<'a>(res: &'a Response, key: &str) -> &'a str {
+    &res.attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .unwrap()
+        .value
+}
+
+/// Moves the block height by `blocks`
+fn env_after(blocks: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height += blocks;
+    env
+}
+
+fn license(licensee: &str) -> ExecuteMsg {
+    ExecuteMsg::License {
+        token_id: "t1".to_string(),
+        licensee: Some(licensee.to_string()),
+    }
+}
+
+/// Auctions an exclusive license of "t1" ending 10 blocks later
+fn start_auction(deps: DepsMut) {
+    let msg = ExecuteMsg::StartAuction {
+        token_id: "t1".to_string(),
+        reserve_price: coin(100, "uatom"),
+        min_increment: Uint128::new(10),
+        end: Expiration::AtHeight(mock_env().block.height + 10),
+        license_duration: Duration::Height(100),
+    };
+    execute(deps, mock_env(), mock_info("alice", &[]), msg).unwrap();
+}
+
+fn place_bid() -> ExecuteMsg {
+    ExecuteMsg::PlaceBid {
+        token_id: "t1".to_string(),
+    }
+}
+
+fn settle_auction() -> ExecuteMsg {
+    ExecuteMsg::SettleAuction {
+        token_id: "t1".to_string(),
+    }
+}
+
+/// Carol disputes "t1" with the minimum bond
+fn open_dispute(deps: DepsMut) {
+    let msg = ExecuteMsg::OpenDispute {
+        token_id: "t1".to_string(),
+        evidence_uri: "ipfs://evidence".to_string(),
+        bond: coin(5, "uatom"),
+    };
+    execute(
+        deps,
+        mock_env(),
+        mock_info("carol", &[coin(5, "uatom")]),
+        msg,
+    )
+    .unwrap();
+}
+
+fn list_for_sale(price: u128) -> ExecuteMsg {
+    ExecuteMsg::ListForSale {
+        token_id: "t1".to_string(),
+        price: coin(price, "uatom"),
+    }
+}
+
+fn buy() -> ExecuteMsg {
+    ExecuteMsg::Buy {
+        token_id: "t1".to_string(),
+    }
+}
+
+#[test]
+fn buy_listed_token() {
+    let mut deps = setup(instantiate_msg());
+    let approve = ExecuteMsg::Approve {
+        spender: "operator".to_string(),
+        token_id: "t1".to_string(),
+        expires: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), approve).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        list_for_sale(100),
+    )
+    .unwrap();
+
+    // the exact price must be sent
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(99, "uatom")]),
+        buy(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+
+    // the royalty is split off the price, the seller gets the rest
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        buy(),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![
+            ("creator".to_string(), vec![coin(10, "uatom")]),
+            ("alice".to_string(), vec![coin(90, "uatom")]),
+        ]
+    );
+    let token = tokens().load(&deps.storage, "t1").unwrap();
+    assert_eq!(token.owner, Addr::unchecked("bob"));
+    assert!(token.approvals.is_empty());
+    assert!(LISTINGS.may_load(&deps.storage, "t1").unwrap().is_none());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(100, "uatom")]),
+        buy(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotListed {});
+}
+
+#[test]
+fn buy_royalty_to_seller() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    let mut mint = mint_msg("t1", CONTENT_ID);
+    mint.royalty = Some(RoyaltyMsg {
+        recipient: "alice".to_string(),
+        share: Decimal::percent(10),
+    });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Mint(mint),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        list_for_sale(100),
+    )
+    .unwrap();
+
+    // a seller who is also the royalty recipient gets the whole price at once
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        buy(),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(100, "uatom")])]
+    );
+}
+
+#[test]
+fn buy_stale_listing() {
+    let mut deps = setup(instantiate_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        list_for_sale(100),
+    )
+    .unwrap();
+
+    // the listing outlived a change of owner
+    tokens()
+        .update(
+            &mut deps.storage,
+            "t1",
+            |token| -> Result<_, ContractError> {
+                let mut token = token.unwrap();
+                token.owner = Addr::unchecked("dave");
+                Ok(token)
+            },
+        )
+        .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        buy(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StaleListing {});
+}
+
+#[test]
+fn place_bids() {
+    let mut deps = setup(instantiate_msg());
+    start_auction(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(99, "uatom")]),
+        place_bid(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidTooLow {
+            min_bid: coin(100, "uatom")
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(105, "uatom")]),
+        place_bid(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BidTooLow {
+            min_bid: coin(110, "uatom")
+        }
+    );
+
+    // the outbid bidder gets its bid back
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(110, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("bob".to_string(), vec![coin(100, "uatom")])]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("dave", &[coin(200, "uatom")]),
+        place_bid(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionEnded {});
+}
+
+#[test]
+fn settle_auction_licenses_winner() {
+    let mut deps = setup(instantiate_msg());
+    start_auction(deps.as_mut());
+
+    // no other license can be sold meanwhile
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(10, "uatom")]),
+        license("carol"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionExists {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(120, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionNotEnded {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "result"), "licensed");
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(120, "uatom")])]
+    );
+    let exclusivity = EXCLUSIVITY.load(&deps.storage, "t1").unwrap();
+    assert_eq!(exclusivity.licensee, Addr::unchecked("bob"));
+
+    let err = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("carol", &[coin(10, "uatom")]),
+        license("carol"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ExclusivelyLicensed {});
+}
+
+#[test]
+fn settle_auction_refunds_unlicensable_token() {
+    let mut deps = setup(instantiate_msg());
+    start_auction(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+    open_dispute(deps.as_mut());
+
+    let res = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "result"), "disputed");
+    assert_eq!(
+        bank_sends(&res),
+        vec![("bob".to_string(), vec![coin(100, "uatom")])]
+    );
+    assert!(!EXCLUSIVITY.has(&deps.storage, "t1"));
+    let license = licenses()
+        .may_load(&deps.storage, (&Addr::unchecked("bob"), "t1"))
+        .unwrap();
+    assert!(license.is_none());
+}
+
+#[test]
+fn no_transfer_during_auction() {
+    let mut deps = setup(instantiate_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        list_for_sale(100),
+    )
+    .unwrap();
+    start_auction(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+
+    let transfer = ExecuteMsg::TransferNft {
+        token_id: "t1".to_string(),
+        recipient: "dave".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        transfer.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionExists {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dave", &[coin(100, "uatom")]),
+        buy(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionExists {});
+
+    // the seller is paid, then the token can move again
+    let res = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(100, "uatom")])]
+    );
+    execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("alice", &[]),
+        transfer,
+    )
+    .unwrap();
+}
+
+#[test]
+fn no_exclusive_license_over_valid_licenses() {
+    let mut deps = setup(instantiate_msg());
+    let grant = ExecuteMsg::GrantLicense {
+        token_id: "t1".to_string(),
+        licensee: "bob".to_string(),
+        price: None,
+        expires: Some(Expiration::AtHeight(mock_env().block.height + 5)),
+        memo: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), grant).unwrap();
+
+    let auction = ExecuteMsg::StartAuction {
+        token_id: "t1".to_string(),
+        reserve_price: coin(100, "uatom"),
+        min_increment: Uint128::new(10),
+        end: Expiration::AtHeight(mock_env().block.height + 10),
+        license_duration: Duration::Height(100),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), auction).unwrap_err();
+    assert_eq!(err, ContractError::LicensesOutstanding {});
+
+    // exclusive terms left over from before the licenses were sold don't apply either
+    let mut licensing = LICENSING.load(&deps.storage, "t1").unwrap();
+    licensing.exclusive = Some(ExclusiveTerms {
+        duration: Duration::Height(100),
+        lock_transfer: false,
+    });
+    LICENSING.save(&mut deps.storage, "t1", &licensing).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(10, "uatom")]),
+        license("carol"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LicensesOutstanding {});
+
+    // once the license expired, the exclusive license can be sold
+    let env = env_after(5);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("carol", &[coin(10, "uatom")]),
+        license("carol"),
+    )
+    .unwrap();
+    assert_eq!(
+        EXCLUSIVITY.load(&deps.storage, "t1").unwrap().licensee,
+        Addr::unchecked("carol")
+    );
+}
+
+#[test]
+fn update_licensing_to_exclusive() {
+    let mut deps = setup(instantiate_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+    let update = ExecuteMsg::UpdateLicensing {
+        token_id: "t1".to_string(),
+        url: "https://license".to_string(),
+        price: coin(20, "uatom"),
+        exclusive: Some(ExclusiveTerms {
+            duration: Duration::Height(100),
+            lock_transfer: true,
+        }),
+        max_licenses: None,
+        refund_window: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        update.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        update.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LicensesOutstanding {});
+
+    // bob's perpetual license is revoked, nothing stands in the way anymore
+    let revoke = ExecuteMsg::RevokeLicense {
+        token_id: "t1".to_string(),
+        licensee: "bob".to_string(),
+        reason: "breach of terms".to_string(),
+        refund: false,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), revoke).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), update).unwrap();
+    let licensing = LICENSING.load(&deps.storage, "t1").unwrap();
+    assert_eq!(licensing.price, coin(20, "uatom"));
+    assert!(licensing.exclusive.unwrap().lock_transfer);
+}
+
+#[test]
+fn place_bid_increment_overflow() {
+    let mut deps = setup(instantiate_msg());
+    let msg = ExecuteMsg::StartAuction {
+        token_id: "t1".to_string(),
+        reserve_price: coin(100, "uatom"),
+        min_increment: Uint128::MAX,
+        end: Expiration::AtHeight(mock_env().block.height + 10),
+        license_duration: Duration::Height(100),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+
+    // no bid can top the first one, but that is an error rather than a panic
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(200, "uatom")]),
+        place_bid(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+fn grant(licensee: &str) -> ExecuteMsg {
+    ExecuteMsg::GrantLicense {
+        token_id: "t1".to_string(),
+        licensee: licensee.to_string(),
+        price: Some(coin(500, "uatom")),
+        expires: None,
+        memo: Some("partner deal".to_string()),
+    }
+}
+
+#[test]
+fn grant_license() {
+    let mut deps = setup(instantiate_msg());
+
+    // only the owner or an approved operator can grant
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        grant("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("bob"),
+    )
+    .unwrap();
+    let license = licenses()
+        .load(&deps.storage, (&Addr::unchecked("bob"), "t1"))
+        .unwrap();
+    assert!(license.granted);
+    assert_eq!(license.price, coin(500, "uatom"));
+    assert_eq!(license.payer, Addr::unchecked("alice"));
+    // the declared price was paid outside of the contract
+    assert_eq!(STATS.load(&deps.storage).unwrap().revenue, vec![]);
+
+    // a valid license can't be granted over
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyLicensed {});
+
+    // nor can a revoked licensee be granted a new license
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("carol"),
+    )
+    .unwrap();
+    let revoke = ExecuteMsg::RevokeLicense {
+        token_id: "t1".to_string(),
+        licensee: "bob".to_string(),
+        reason: "breach of terms".to_string(),
+        refund: false,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), revoke).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LicenseRevoked {});
+}
+
+#[test]
+fn grant_license_during_auction() {
+    let mut deps = setup(instantiate_msg());
+    start_auction(deps.as_mut());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AuctionExists {});
+}
+
+fn revoke(licensee: &str, refund: bool) -> ExecuteMsg {
+    ExecuteMsg::RevokeLicense {
+        token_id: "t1".to_string(),
+        licensee: licensee.to_string(),
+        reason: "breach of terms".to_string(),
+        refund,
+    }
+}
+
+#[test]
+fn revoke_license() {
+    let mut deps = setup(instantiate_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        revoke("bob", false),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // funds are only accepted when the owner pays the refund
+    let funds = [coin(10, "uatom")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &funds),
+        revoke("bob", false),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        revoke("bob", true),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &funds),
+        revoke("bob", true),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("bob".to_string(), vec![coin(10, "uatom")])]
+    );
+    let revoked = licenses()
+        .load(&deps.storage, (&Addr::unchecked("bob"), "t1"))
+        .unwrap();
+    assert!(revoked.revoked.unwrap().refunded);
+
+    // revoked licensees can't buy their way back in
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        license("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LicenseRevoked {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &funds),
+        revoke("bob", true),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::LicenseRevoked {});
+}
+
+#[test]
+fn revoke_escrowed_license() {
+    let mut deps = setup(instantiate_msg());
+    update_licensing(deps.as_mut(), None, Some(Duration::Height(5)));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+
+    // the escrowed payment covers the refund, the owner doesn't pay
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(10, "uatom")]),
+        revoke("bob", true),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFunds { .. }));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        revoke("bob", true),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("bob".to_string(), vec![coin(10, "uatom")])]
+    );
+}
+
+#[test]
+fn revoke_granted_license() {
+    let mut deps = setup(instantiate_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        grant("bob"),
+    )
+    .unwrap();
+
+    // the declared price was never paid to the contract, there is nothing to refund
+    let funds = [coin(500, "uatom")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &funds),
+        revoke("bob", true),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotRefundable {});
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        revoke("bob", false),
+    )
+    .unwrap();
+    assert!(bank_sends(&res).is_empty());
+}
+
+/// Sets non-exclusive licensing terms on "t1" at 10uatom
+fn update_licensing(deps: DepsMut, max_licenses: Option<u64>, refund_window: Option<Duration>) {
+    let msg = ExecuteMsg::UpdateLicensing {
+        token_id: "t1".to_string(),
+        url: "https://license".to_string(),
+        price: coin(10, "uatom"),
+        exclusive: None,
+        max_licenses,
+        refund_window,
+    };
+    execute(deps, mock_env(), mock_info("alice", &[]), msg).unwrap();
+}
+
+fn request_refund() -> ExecuteMsg {
+    ExecuteMsg::RequestRefund {
+        token_id: "t1".to_string(),
+    }
+}
+
+#[test]
+fn refund_within_window() {
+    let mut deps = setup(instantiate_msg());
+    update_licensing(deps.as_mut(), Some(1), Some(Duration::Height(5)));
+    let funds = [coin(10, "uatom")];
+
+    // the payment is held until the window closes
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        license("bob"),
+    )
+    .unwrap();
+    assert!(bank_sends(&res).is_empty());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &funds),
+        license("carol"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        request_refund(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoEscrow {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        request_refund(),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("bob".to_string(), vec![coin(10, "uatom")])]
+    );
+
+    // the purchase is undone, the license is gone and back in the supply
+    let bob = Addr::unchecked("bob");
+    assert!(licenses()
+        .may_load(&deps.storage, (&bob, "t1"))
+        .unwrap()
+        .is_none());
+    assert!(ESCROWS
+        .may_load(&deps.storage, (&bob, "t1"))
+        .unwrap()
+        .is_none());
+    assert_eq!(LICENSES_SOLD.load(&deps.storage, "t1").unwrap(), 0);
+
+    // so the licensee isn't locked out
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        license("bob"),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env_after(5),
+        mock_info("bob", &[]),
+        request_refund(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RefundWindowClosed {});
+}
+
+#[test]
+fn release_escrow() {
+    let mut deps = setup(instantiate_msg());
+    update_licensing(deps.as_mut(), None, Some(Duration::Height(5)));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+
+    let release = ExecuteMsg::ReleaseEscrow {
+        token_id: "t1".to_string(),
+        licensee: "bob".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        release.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RefundWindowOpen {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_after(5),
+        mock_info("anyone", &[]),
+        release,
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(10, "uatom")])]
+    );
+    let err = execute(
+        deps.as_mut(),
+        env_after(5),
+        mock_info("bob", &[]),
+        request_refund(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoEscrow {});
+}
+
+fn license_batch(token_ids: &[&str]) -> ExecuteMsg {
+    let items = token_ids
+        .iter()
+        .map(|token_id| LicenseItem {
+            token_id: token_id.to_string(),
+            licensee: None,
+        })
+        .collect();
+    ExecuteMsg::LicenseBatch { items }
+}
+
+/// Adds "t2" owned by alice and "t3" owned by bob to the setup
+fn setup_batch() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = setup(instantiate_msg());
+    let mint = ExecuteMsg::Mint(mint_msg("t2", "c2"));
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), mint).unwrap();
+    let mint = ExecuteMsg::Mint(mint_msg("t3", "c3"));
+    execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), mint).unwrap();
+    deps
+}
+
+#[test]
+fn license_batch_pays_each_owner_once() {
+    let mut deps = setup_batch();
+    let funds = [coin(30, "uatom")];
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &funds),
+        license_batch(&["t1", "t2", "t3"]),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![
+            ("alice".to_string(), vec![coin(20, "uatom")]),
+            ("bob".to_string(), vec![coin(10, "uatom")]),
+        ]
+    );
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(attribute(&res, "count"), "3");
+    let carol = Addr::unchecked("carol");
+    for token_id in ["t1", "t2", "t3"].iter() {
+        assert!(licenses()
+            .load(&deps.storage, (&carol, token_id))
+            .unwrap()
+            .is_valid(&mock_env().block));
+    }
+}
+
+#[test]
+fn license_batch_failures() {
+    let mut deps = setup_batch();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        license_batch(&[]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmptyBatch {});
+
+    // the whole batch must be paid for at once
+    let mut deps = setup_batch();
+    let funds = [coin(20, "uatom")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &funds),
+        license_batch(&["t1", "t2", "t3"]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidFunds {
+            expected: "30uatom".to_string()
+        }
+    );
+
+    // an item failing fails the batch, including an item licensed earlier in the same batch
+    let mut deps = setup_batch();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &funds),
+        license_batch(&["t1", "t1"]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyLicensed {});
+    let mut deps = setup_batch();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &funds),
+        license_batch(&["t1", "t9"]),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
+
+fn quote(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    licensee: Option<&str>,
+) -> QuoteResponse {
+    let msg = QueryMsg::QuoteLicense {
+        token_id: "t1".to_string(),
+        licensee: licensee.map(String::from),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn quote_license() {
+    let mut deps = setup(instantiate_msg());
+    update_licensing(deps.as_mut(), Some(1), None);
+    let quoted = quote(&deps, Some("bob"));
+    assert_eq!(quoted.funds, vec![coin(10, "uatom")]);
+    assert_eq!(
+        quoted.payouts,
+        vec![Payout {
+            recipient: Addr::unchecked("alice"),
+            amount: coin(10, "uatom"),
+        }]
+    );
+    assert_eq!(quoted.error, None);
+
+    // the quote fails like the purchase would, licensee checks first
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyLicensed {});
+    assert_eq!(quote(&deps, Some("bob")).error, Some(err.to_string()));
+    assert_eq!(
+        quote(&deps, None).error,
+        Some(ContractError::SoldOut {}.to_string())
+    );
+}
+
+#[test]
+fn mint_batch() {
+    let mut msg = instantiate_msg();
+    msg.max_mint_batch = Some(2);
+    let mut deps = setup(msg);
+
+    let batch = vec![mint_msg("t2", "c2"), mint_msg("t3", "c3")];
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::MintBatch(batch),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "count"), "2");
+    assert_eq!(num_tokens(&deps.storage).unwrap(), 3);
+    assert_eq!(
+        tokens().load(&deps.storage, "t3").unwrap().owner,
+        Addr::unchecked("bob")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::MintBatch(vec![]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmptyBatch {});
+    let batch = vec![
+        mint_msg("t4", "c4"),
+        mint_msg("t5", "c5"),
+        mint_msg("t6", "c6"),
+    ];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::MintBatch(batch),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BatchTooLarge { max: 2 });
+}
+
+#[test]
+fn mint_batch_duplicates() {
+    // duplicates inside the batch are claimed like stored ones
+    let mut deps = setup(instantiate_msg());
+    let batch = vec![mint_msg("t2", "c2"), mint_msg("t2", "c3")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::MintBatch(batch),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+
+    let mut deps = setup(instantiate_msg());
+    let batch = vec![mint_msg("t2", "c2"), mint_msg("t3", "c2")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::MintBatch(batch),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+    assert_eq!(num_tokens(&deps.storage).unwrap(), 1);
+}
+
+fn page<T: DeserializeOwned>(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: QueryMsg,
+) -> T {
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn all_tokens(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    start_after: Option<&str>,
+    end_before: Option<&str>,
+    order: OrderBy,
+) -> Vec<String> {
+    let msg = QueryMsg::AllTokens {
+        include_hidden: None,
+        start_after: start_after.map(String::from),
+        end_before: end_before.map(String::from),
+        limit: None,
+        order: Some(order),
+    };
+    page::<TokensResponse>(deps, msg).tokens
+}
+
+#[test]
+fn paginate_tokens() {
+    let mut deps = setup_batch();
+    let mint = ExecuteMsg::Mint(mint_msg("t4", "c4"));
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), mint).unwrap();
+
+    assert_eq!(
+        all_tokens(&deps, None, None, OrderBy::Asc),
+        ["t1", "t2", "t3", "t4"]
+    );
+    assert_eq!(
+        all_tokens(&deps, None, None, OrderBy::Desc),
+        ["t4", "t3", "t2", "t1"]
+    );
+    assert_eq!(
+        all_tokens(&deps, Some("t1"), Some("t4"), OrderBy::Asc),
+        ["t2", "t3"]
+    );
+    // bounds follow the iteration order, descending pages start after the higher key
+    assert_eq!(
+        all_tokens(&deps, Some("t4"), Some("t1"), OrderBy::Desc),
+        ["t3", "t2"]
+    );
+    assert_eq!(
+        all_tokens(&deps, Some("t3"), None, OrderBy::Desc),
+        ["t2", "t1"]
+    );
+    assert_eq!(
+        all_tokens(&deps, None, Some("t2"), OrderBy::Desc),
+        ["t4", "t3"]
+    );
+    assert!(all_tokens(&deps, Some("t1"), Some("t4"), OrderBy::Desc).is_empty());
+
+    // "t3" is owned by bob
+    let msg = QueryMsg::Tokens {
+        owner: "alice".to_string(),
+        include_hidden: None,
+        start_after: Some("t4".to_string()),
+        end_before: None,
+        limit: Some(1),
+        order: Some(OrderBy::Desc),
+    };
+    assert_eq!(page::<TokensResponse>(&deps, msg).tokens, ["t2"]);
+    let msg = QueryMsg::Tokens {
+        owner: "alice".to_string(),
+        include_hidden: None,
+        start_after: None,
+        end_before: Some("t1".to_string()),
+        limit: None,
+        order: Some(OrderBy::Desc),
+    };
+    assert_eq!(page::<TokensResponse>(&deps, msg).tokens, ["t4", "t2"]);
+}
+
+#[test]
+fn paginate_licenses() {
+    let mut deps = setup_batch();
+    let funds = [coin(30, "uatom")];
+    let msg = license_batch(&["t1", "t2", "t3"]);
+    execute(deps.as_mut(), mock_env(), mock_info("cat", &funds), msg).unwrap();
+    for licensee in ["bob", "dan"] {
+        let funds = [coin(10, "uatom")];
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(licensee, &funds),
+            license(licensee),
+        )
+        .unwrap();
+    }
+
+    let msg = QueryMsg::Licenses {
+        licensee: "cat".to_string(),
+        start_after: Some("t3".to_string()),
+        end_before: Some("t1".to_string()),
+        limit: None,
+        order: Some(OrderBy::Desc),
+    };
+    let licenses = page::<LicensesResponse>(&deps, msg).licenses;
+    let token_ids: Vec<_> = licenses.iter().map(|l| l.token_id.as_str()).collect();
+    assert_eq!(token_ids, ["t2"]);
+    let msg = QueryMsg::Licenses {
+        licensee: "cat".to_string(),
+        start_after: None,
+        end_before: None,
+        limit: Some(2),
+        order: Some(OrderBy::Desc),
+    };
+    let licenses = page::<LicensesResponse>(&deps, msg).licenses;
+    let token_ids: Vec<_> = licenses.iter().map(|l| l.token_id.as_str()).collect();
+    assert_eq!(token_ids, ["t3", "t2"]);
+
+    // licensees are length prefixed in the index, equal length names sort alphabetically
+    let token_licenses = |start_after: Option<&str>, end_before: Option<&str>, order| {
+        let msg = QueryMsg::TokenLicenses {
+            token_id: "t1".to_string(),
+            start_after: start_after.map(String::from),
+            end_before: end_before.map(String::from),
+            limit: None,
+            order: Some(order),
+        };
+        let licenses = page::<LicensesResponse>(&deps, msg).licenses;
+        licenses
+            .into_iter()
+            .map(|l| l.licensee.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        token_licenses(None, None, OrderBy::Desc),
+        ["dan", "cat", "bob"]
+    );
+    assert_eq!(
+        token_licenses(Some("bob"), Some("dan"), OrderBy::Asc),
+        ["cat"]
+    );
+    assert_eq!(
+        token_licenses(Some("dan"), Some("bob"), OrderBy::Desc),
+        ["cat"]
+    );
+    assert_eq!(
+        token_licenses(None, Some("bob"), OrderBy::Desc),
+        ["dan", "cat"]
+    );
+}
+
+#[test]
+fn paginate_approvals() {
+    let mut deps = setup(instantiate_msg());
+    for operator in ["op1", "op2", "op3"] {
+        let msg = ExecuteMsg::ApproveAll {
+            operator: operator.to_string(),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    }
+
+    let operators = |start_after: Option<&str>, end_before: Option<&str>, order| {
+        let msg = QueryMsg::ApprovedForAll {
+            owner: "alice".to_string(),
+            include_expired: None,
+            start_after: start_after.map(String::from),
+            end_before: end_before.map(String::from),
+            limit: None,
+            order: Some(order),
+        };
+        let res = page::<ApprovedForAllResponse>(&deps, msg);
+        res.operators
+            .into_iter()
+            .map(|approval| approval.spender)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(operators(None, None, OrderBy::Desc), ["op3", "op2", "op1"]);
+    assert_eq!(operators(Some("op1"), None, OrderBy::Asc), ["op2", "op3"]);
+    assert_eq!(operators(Some("op3"), Some("op1"), OrderBy::Desc), ["op2"]);
+    assert_eq!(operators(None, Some("op2"), OrderBy::Desc), ["op3"]);
+}
+
+#[test]
+fn paginate_moderation_log() {
+    let mut deps = setup_batch();
+    for (token_id, hidden) in [("t1", true), ("t2", true), ("t1", false), ("t2", false)] {
+        let msg = ExecuteMsg::Moderate {
+            token_id: token_id.to_string(),
+            hidden,
+            suspended: false,
+            reason: ReasonCode::Spam,
+            forfeit_deposit: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    }
+
+    let log = |token_id: Option<&str>, start_after, end_before, order| {
+        let msg = QueryMsg::ModerationLog {
+            token_id: token_id.map(String::from),
+            start_after,
+            end_before,
+            limit: None,
+            order: Some(order),
+        };
+        let res = page::<ModerationLogResponse>(&deps, msg);
+        res.entries
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>()
+    };
+    let first = log(None, None, None, OrderBy::Asc)[0];
+    let ids: Vec<u64> = (first..first + 4).collect();
+    assert_eq!(log(None, None, None, OrderBy::Asc), ids);
+    assert_eq!(
+        log(None, None, None, OrderBy::Desc),
+        [ids[3], ids[2], ids[1], ids[0]]
+    );
+    assert_eq!(
+        log(None, Some(ids[3]), Some(ids[0]), OrderBy::Desc),
+        [ids[2], ids[1]]
+    );
+    assert_eq!(
+        log(None, Some(ids[0]), Some(ids[3]), OrderBy::Asc),
+        [ids[1], ids[2]]
+    );
+    assert_eq!(log(Some("t1"), None, None, OrderBy::Desc), [ids[2], ids[0]]);
+    assert_eq!(log(Some("t2"), None, Some(ids[1]), OrderBy::Desc), [ids[3]]);
+}
+
+fn stats(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> (StatsResponse, TokenStatsResponse) {
+    let stats = page(deps, QueryMsg::Stats {});
+    let msg = QueryMsg::TokenStats {
+        token_id: "t1".to_string(),
+    };
+    (stats, page(deps, msg))
+}
+
+#[test]
+fn refunds_revert_stats() {
+    let mut deps = setup(instantiate_msg());
+    update_licensing(deps.as_mut(), None, Some(Duration::Height(5)));
+    let funds = [coin(10, "uatom")];
+    for licensee in ["bob", "carol"] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(licensee, &funds),
+            license(licensee),
+        )
+        .unwrap();
+    }
+    let (registry, token) = stats(&deps);
+    assert_eq!(registry.licenses_sold, 2);
+    assert_eq!(registry.licensees, 2);
+    assert_eq!(registry.revenue, vec![coin(20, "uatom")]);
+    assert_eq!(token.licenses_sold, 2);
+    assert_eq!(token.revenue, vec![coin(20, "uatom")]);
+
+    // a refund removes the license with its licensee
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        request_refund(),
+    )
+    .unwrap();
+    let (registry, token) = stats(&deps);
+    assert_eq!(registry.licenses_sold, 1);
+    assert_eq!(registry.licensees, 1);
+    assert_eq!(registry.revenue, vec![coin(10, "uatom")]);
+    assert_eq!(token.licenses_sold, 1);
+    assert_eq!(token.revenue, vec![coin(10, "uatom")]);
+
+    // a refunded revocation keeps the licensee on record
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        revoke("carol", true),
+    )
+    .unwrap();
+    let (registry, token) = stats(&deps);
+    assert_eq!(registry.licenses_sold, 0);
+    assert_eq!(registry.licensees, 1);
+    assert_eq!(registry.revenue, vec![]);
+    assert_eq!(token.licenses_sold, 0);
+    assert_eq!(token.revenue, vec![]);
+}
+
+#[test]
+fn earliest_registration_scan_limit() {
+    let mut deps = setup(instantiate_msg());
+    let mint = ExecuteMsg::Mint(mint_msg("t2", SIMILAR_CONTENT_ID));
+    execute(deps.as_mut(), env_after(1), mock_info("bob", &[]), mint).unwrap();
+
+    let search = |start_after: Option<&str>| QueryMsg::EarliestRegistration {
+        unit: IsccUnit::ContentId(SIMILAR_CONTENT_ID.to_string()),
+        max_distance: Some(8),
+        start_after: start_after.map(String::from),
+        limit: Some(1),
+    };
+    // the scan stops after one registration and tells where to resume
+    let res: EarliestRegistrationResponse = page(&deps, search(None));
+    let registration = res.registration.unwrap();
+    assert_eq!(registration.token_id, "t1");
+    assert!(registration.distance > 0);
+    assert_eq!(res.next_start_after, Some("t1".to_string()));
+
+    let res: EarliestRegistrationResponse = page(&deps, search(Some("t1")));
+    let registration = res.registration.unwrap();
+    assert_eq!(registration.token_id, "t2");
+    assert_eq!(registration.distance, 0);
+    assert_eq!(res.next_start_after, None);
+}
+
+fn resolve_dispute(ruling: DisputeRuling) -> ExecuteMsg {
+    ExecuteMsg::ResolveDispute {
+        token_id: "t1".to_string(),
+        ruling,
+    }
+}
+
+#[test]
+fn dispute_ruled_for_claimant() {
+    let mut msg = instantiate_msg();
+    msg.mint_deposit = Some(MintDeposit {
+        amount: coin(50, "uatom"),
+        clean_period: Duration::Height(100),
+    });
+    let mut deps = setup(msg);
+
+    // only the claimant's bond is accepted and the owner can't dispute its own token
+    let open = |bond| ExecuteMsg::OpenDispute {
+        token_id: "t1".to_string(),
+        evidence_uri: "ipfs://evidence".to_string(),
+        bond,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(1, "uatom")]),
+        open(coin(1, "uatom")),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidBond {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(5, "uatom")]),
+        open(coin(5, "uatom")),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    open_dispute(deps.as_mut());
+
+    // the token and its licensing terms are frozen while the dispute is open
+    let transfer = ExecuteMsg::TransferNft {
+        token_id: "t1".to_string(),
+        recipient: "dave".to_string(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), transfer).unwrap_err();
+    assert_eq!(err, ContractError::Disputed {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        list_for_sale(100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Disputed {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Disputed {});
+    let update = ExecuteMsg::UpdateLicensing {
+        token_id: "t1".to_string(),
+        url: "https://license".to_string(),
+        price: coin(0, "uatom"),
+        exclusive: None,
+        max_licenses: None,
+        refund_window: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), update).unwrap_err();
+    assert_eq!(err, ContractError::Disputed {});
+    assert_eq!(
+        LICENSING.load(&deps.storage, "t1").unwrap().price,
+        coin(10, "uatom")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        resolve_dispute(DisputeRuling::Claimant),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("judge", &[]),
+        resolve_dispute(DisputeRuling::Claimant),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("carol".to_string(), vec![coin(5, "uatom")])]
+    );
+
+    // the claimant takes over the token, its ISCC claim and deposit, the royalty is void
+    let carol = Addr::unchecked("carol");
+    assert_eq!(tokens().load(&deps.storage, "t1").unwrap().owner, carol);
+    assert_eq!(
+        iscc_data().load(&deps.storage, "t1").unwrap().registrant,
+        carol
+    );
+    assert_eq!(DEPOSITS.load(&deps.storage, "t1").unwrap().depositor, carol);
+    assert!(!ROYALTIES.has(&deps.storage, "t1"));
+    assert!(!DISPUTES.has(&deps.storage, "t1"));
+}
+
+#[test]
+fn dispute_ruled_for_owner() {
+    let mut deps = setup(instantiate_msg());
+    open_dispute(deps.as_mut());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("judge", &[]),
+        resolve_dispute(DisputeRuling::Owner),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(5, "uatom")])]
+    );
+    assert_eq!(
+        tokens().load(&deps.storage, "t1").unwrap().owner,
+        Addr::unchecked("alice")
+    );
+    assert!(ROYALTIES.has(&deps.storage, "t1"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("judge", &[]),
+        resolve_dispute(DisputeRuling::Owner),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoDispute {});
+}
+
+#[test]
+fn update_arbitrators() {
+    let mut deps = setup(instantiate_msg());
+    let update = |add: &[&str], remove: &[&str]| ExecuteMsg::UpdateArbitrators {
+        add: add.iter().map(|addr| addr.to_string()).collect(),
+        remove: remove.iter().map(|addr| addr.to_string()).collect(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("judge", &[]),
+        update(&["bob"], &[]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+
+    // without arbitrators nobody could resolve a dispute
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        update(&[], &["judge"]),
+    )
+    .unwrap();
+    let open = ExecuteMsg::OpenDispute {
+        token_id: "t1".to_string(),
+        evidence_uri: "ipfs://evidence".to_string(),
+        bond: coin(5, "uatom"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[coin(5, "uatom")]),
+        open,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoArbitrators {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        update(&["jury", "jury"], &[]),
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap().arbitrators,
+        vec![Addr::unchecked("jury")]
+    );
+    open_dispute(deps.as_mut());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("judge", &[]),
+        resolve_dispute(DisputeRuling::Owner),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jury", &[]),
+        resolve_dispute(DisputeRuling::Owner),
+    )
+    .unwrap();
+}
+
+#[test]
+fn hidden_tokens_filtered() {
+    let mut deps = setup_batch();
+    for token_id in ["t1", "t2"] {
+        let msg = ExecuteMsg::ListForSale {
+            token_id: token_id.to_string(),
+            price: coin(100, "uatom"),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    }
+    let hide = ExecuteMsg::Moderate {
+        token_id: "t1".to_string(),
+        hidden: true,
+        suspended: false,
+        reason: ReasonCode::Spam,
+        forfeit_deposit: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        hide.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), hide).unwrap();
+
+    let all_tokens = |include_hidden| {
+        let msg = QueryMsg::AllTokens {
+            include_hidden,
+            start_after: None,
+            end_before: None,
+            limit: None,
+            order: None,
+        };
+        page::<TokensResponse>(&deps, msg).tokens
+    };
+    assert_eq!(all_tokens(None), ["t2", "t3"]);
+    assert_eq!(all_tokens(Some(true)), ["t1", "t2", "t3"]);
+
+    let owned_tokens = |include_hidden| {
+        let msg = QueryMsg::Tokens {
+            owner: "alice".to_string(),
+            include_hidden,
+            start_after: None,
+            end_before: None,
+            limit: None,
+            order: None,
+        };
+        page::<TokensResponse>(&deps, msg).tokens
+    };
+    assert_eq!(owned_tokens(None), ["t2"]);
+    assert_eq!(owned_tokens(Some(true)), ["t1", "t2"]);
+
+    let listings = |include_hidden| {
+        let msg = QueryMsg::Listings {
+            include_hidden,
+            start_after: None,
+            end_before: None,
+            limit: None,
+            order: None,
+        };
+        let res = page::<ListingsResponse>(&deps, msg);
+        res.listings
+            .into_iter()
+            .map(|listing| listing.token_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(listings(None), ["t2"]);
+    assert_eq!(listings(Some(true)), ["t1", "t2"]);
+
+    // the token itself can still be looked up
+    let msg = QueryMsg::NftInfo {
+        token_id: "t1".to_string(),
+    };
+    query(deps.as_ref(), mock_env(), msg).unwrap();
+}
+
+fn set_pause(deps: DepsMut, license: bool) {
+    let pause = PauseFlags {
+        license,
+        ..PauseFlags::default()
+    };
+    execute(
+        deps,
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::SetPause(pause),
+    )
+    .unwrap();
+}
+
+#[test]
+fn paused_licensing() {
+    let mut deps = setup(instantiate_msg());
+    start_auction(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(100, "uatom")]),
+        place_bid(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::SetPause(PauseFlags::default()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+    set_pause(deps.as_mut(), true);
+
+    let err = execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+    assert_eq!(
+        quote(&deps, None).error,
+        Some(ContractError::Paused {}.to_string())
+    );
+    let msg = QueryMsg::Token {
+        token_id: "t1".to_string(),
+        include_expired: None,
+    };
+    let token: TokenResponse = page(&deps, msg);
+    assert_eq!(token.licensing_status, LicensingStatus::Paused);
+
+    // the auction settles once licensing resumes
+    set_pause(deps.as_mut(), false);
+    execute(
+        deps.as_mut(),
+        env_after(10),
+        mock_info("anyone", &[]),
+        settle_auction(),
+    )
+    .unwrap();
+    let bob = Addr::unchecked("bob");
+    assert!(licenses().load(&deps.storage, (&bob, "t1")).is_ok());
+}
+
+fn admin(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> AdminResponse {
+    page(deps, QueryMsg::Admin {})
+}
+
+#[test]
+fn admin_handover() {
+    let mut deps = setup(instantiate_msg());
+    let propose = |admin: &str| ExecuteMsg::ProposeAdmin {
+        admin: admin.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        propose("bob"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        propose("bob"),
+    )
+    .unwrap();
+
+    // the admin stays in charge until the proposed admin accepts
+    let res = admin(&deps);
+    assert_eq!(res.admin, Some(Addr::unchecked("alice")));
+    assert_eq!(res.pending_admin, Some(Addr::unchecked("bob")));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotPendingAdmin {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    )
+    .unwrap();
+    let res = admin(&deps);
+    assert_eq!(res.admin, Some(Addr::unchecked("bob")));
+    assert_eq!(res.pending_admin, None);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        propose("alice"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+
+    // renouncing drops a pending proposal too, nobody can take over afterwards
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        propose("carol"),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        ExecuteMsg::RenounceAdmin {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::RenounceAdmin {},
+    )
+    .unwrap();
+    let res = admin(&deps);
+    assert_eq!(res.admin, None);
+    assert_eq!(res.pending_admin, None);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotPendingAdmin {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::SetPause(PauseFlags::default()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+}
+
+fn deposit_msg() -> InstantiateMsg {
+    let mut msg = instantiate_msg();
+    msg.mint_deposit = Some(MintDeposit {
+        amount: coin(50, "uatom"),
+        clean_period: Duration::Height(100),
+    });
+    msg
+}
+
+#[test]
+fn forfeit_deposit() {
+    let funds = mint_funds(&deposit_msg());
+    let mut deps = setup(deposit_msg());
+    let moderate = |token_id: &str| ExecuteMsg::Moderate {
+        token_id: token_id.to_string(),
+        hidden: true,
+        suspended: true,
+        reason: ReasonCode::Infringement,
+        forfeit_deposit: Some(true),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        moderate("t1"),
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(50, "uatom")])]
+    );
+    assert_eq!(DEPOSITS_HELD.load(&deps.storage).unwrap(), vec![]);
+
+    // without an admin there is nobody to forfeit the deposit to
+    let mint = ExecuteMsg::Mint(mint_msg("t2", "c2"));
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &funds), mint).unwrap();
+    let update = ExecuteMsg::UpdateModerators {
+        add: vec!["mod".to_string()],
+        remove: vec![],
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), update).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::RenounceAdmin {},
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("mod", &[]),
+        moderate("t2"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoForfeitRecipient {});
+}
+
+fn burn() -> ExecuteMsg {
+    ExecuteMsg::Burn {
+        token_id: "t1".to_string(),
+    }
+}
+
+#[test]
+fn burn_refunds_deposit() {
+    let funds = mint_funds(&deposit_msg());
+    let mut deps = setup(deposit_msg());
+    let err = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), burn()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the deposit is refunded before the clean period ended
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn()).unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("alice".to_string(), vec![coin(50, "uatom")])]
+    );
+    assert_eq!(DEPOSITS_HELD.load(&deps.storage).unwrap(), vec![]);
+    assert!(!DEPOSITS.has(&deps.storage, "t1"));
+    assert!(!LICENSING.has(&deps.storage, "t1"));
+    assert!(tokens().may_load(&deps.storage, "t1").unwrap().is_none());
+    assert_eq!(num_tokens(&deps.storage).unwrap(), 0);
+    let registry: StatsResponse = page(&deps, QueryMsg::Stats {});
+    assert_eq!(registry.total_tokens, 0);
+    assert_eq!(registry.owners, 0);
+
+    // the token id and ISCC code can be registered again
+    let mint = ExecuteMsg::Mint(mint_msg("t1", CONTENT_ID));
+    execute(deps.as_mut(), mock_env(), mock_info("bob", &funds), mint).unwrap();
+}
+
+#[test]
+fn burn_failures() {
+    // licensees keep referring to the token
+    let mut deps = setup(deposit_msg());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(10, "uatom")]),
+        license("bob"),
+    )
+    .unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn()).unwrap_err();
+    assert_eq!(err, ContractError::LicensesOnRecord {});
+
+    // a disputed token keeps its deposit at stake
+    let mut deps = setup(deposit_msg());
+    open_dispute(deps.as_mut());
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn()).unwrap_err();
+    assert_eq!(err, ContractError::Disputed {});
+    assert!(DEPOSITS.has(&deps.storage, "t1"));
+}
+
+#[test]
+fn mint_fee_forwarded_to_treasury() {
+    let mut msg = deposit_msg();
+    msg.mint_fee = Some(MintFeeMsg {
+        amount: coin(7, "uatom"),
+        treasury: "treasury".to_string(),
+    });
+    let funds = mint_funds(&msg);
+    let mut deps = setup(msg);
+
+    // the fee comes on top of the deposit
+    let mint = ExecuteMsg::Mint(mint_msg("t2", "c2"));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(50, "uatom")]),
+        mint.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidFunds {
+            expected: "57uatom".to_string()
+        }
+    );
+    let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &funds), mint).unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("treasury".to_string(), vec![coin(7, "uatom")])]
+    );
+    assert_eq!(attribute(&res, "fee"), "7uatom");
+
+    // a batch pays the fee of every token at once, only deposits are held
+    let batch = ExecuteMsg::MintBatch(vec![mint_msg("t3", "c3"), mint_msg("t4", "c4")]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(114, "uatom")]),
+        batch,
+    )
+    .unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![("treasury".to_string(), vec![coin(14, "uatom")])]
+    );
+    assert_eq!(
+        DEPOSITS_HELD.load(&deps.storage).unwrap(),
+        vec![coin(200, "uatom")]
+    );
+
+    // without a fee nothing is forwarded
+    let set_fee = ExecuteMsg::SetMintFee { mint_fee: None };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        set_fee.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {});
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), set_fee).unwrap();
+    let mint = ExecuteMsg::Mint(mint_msg("t5", "c5"));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[coin(50, "uatom")]),
+        mint,
+    )
+    .unwrap();
+    assert!(bank_sends(&res).is_empty());
+}
+
+/// Mock API able to humanize the 20 byte addresses derived from public keys
+struct VoucherApi(MockApi);
+
+impl Api for VoucherApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.0.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.0.addr_canonicalize(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        let hex: String = canonical
+            .as_slice()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Ok(Addr::unchecked(hex))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        hash: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(hash, signature, pubkey)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0
+            .secp256k1_recover_pubkey(hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        pubkey: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, pubkey)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        pubkeys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_batch_verify(messages, signatures, pubkeys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+fn sign_voucher(key: &SigningKey, voucher: &MintVoucher) -> Binary {
+    let signature: Signature = key.sign_digest(Sha256::new().chain(to_vec(voucher).unwrap()));
+    Binary::from(signature.as_ref())
+}
+
+fn redeem(voucher: &MintVoucher, signature: Binary) -> ExecuteMsg {
+    ExecuteMsg::MintAndLicense {
+        voucher: voucher.clone(),
+        signature,
+        licensee: None,
+    }
+}
+
+#[test]
+fn mint_and_license() {
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: VoucherApi(MockApi::default()),
+        querier: MockQuerier::<Empty>::new(&[]),
+    };
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        deposit_msg(),
+    )
+    .unwrap();
+
+    let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let pubkey = EncodedPoint::from(&key.verifying_key()).compress();
+    let voucher = MintVoucher {
+        creator_pubkey: Binary::from(pubkey.as_bytes()),
+        contract_address: mock_env().contract.address.to_string(),
+        nonce: 1,
+        expires: None,
+        mint: mint_msg("v1", CONTENT_ID),
+    };
+    let signature = sign_voucher(&key, &voucher);
+    let funds = [coin(60, "uatom")];
+
+    // the signature covers the whole voucher
+    let mut tampered = voucher.clone();
+    tampered.nonce = 2;
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        redeem(&tampered, signature.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSignature {});
+
+    // the same key uncompressed would derive another creator account
+    let mut uncompressed = voucher.clone();
+    uncompressed.creator_pubkey = Binary::from(pubkey.decompress().unwrap().as_bytes());
+    let signature_uncompressed = sign_voucher(&key, &uncompressed);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        redeem(&uncompressed, signature_uncompressed),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPubkey {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        redeem(&voucher, signature.clone()),
+    )
+    .unwrap();
+    let creator = secp256k1_address(&deps.api, pubkey.as_bytes()).unwrap();
+    assert_eq!(
+        bank_sends(&res),
+        vec![(creator.to_string(), vec![coin(10, "uatom")])]
+    );
+    assert_eq!(tokens().load(&deps.storage, "v1").unwrap().owner, creator);
+    let bob = Addr::unchecked("bob");
+    assert!(licenses()
+        .load(&deps.storage, (&bob, "v1"))
+        .unwrap()
+        .is_valid(&mock_env().block));
+    // the sender paid the deposit, so it gets it back
+    assert_eq!(DEPOSITS.load(&deps.storage, "v1").unwrap().depositor, bob);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &funds),
+        redeem(&voucher, signature),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NonceUsed {});
+}
+
+fn authorship(key: &SigningKey, code: &str) -> Authorship {
+    let signature: Signature = key.sign_digest(Sha256::new().chain(code));
+    Authorship {
+        scheme: SignatureScheme::Secp256k1,
+        pubkey: Binary::from(
+            EncodedPoint::from(&key.verifying_key())
+                .compress()
+                .as_bytes(),
+        ),
+        signature: Binary::from(signature.as_ref()),
+    }
+}
+
+#[test]
+fn mint_with_authorship() {
+    let mut deps = setup(instantiate_msg());
+    let key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let mut msg = mint_msg("t2", "c2");
+
+    // the proof must sign the full ISCC code of the minted work
+    msg.authorship = Some(authorship(&key, "meta-c2-data-t2-instance-t1"));
+    let mint = ExecuteMsg::Mint(msg.clone());
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), mint).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAuthorship {});
+    let mut garbled = authorship(&key, "meta-c2-data-t2-instance-t2");
+    garbled.signature = Binary::from(vec![1u8; 64]);
+    msg.authorship = Some(garbled);
+    let mint = ExecuteMsg::Mint(msg.clone());
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), mint).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAuthorship {});
+    assert!(tokens().may_load(&deps.storage, "t2").unwrap().is_none());
+
+    let proof = authorship(&key, "meta-c2-data-t2-instance-t2");
+    msg.authorship = Some(proof.clone());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Mint(msg),
+    )
+    .unwrap();
+    assert_eq!(
+        iscc_data().load(&deps.storage, "t2").unwrap().authorship,
+        Some(proof)
+    );
+}
@@ -0,0 +1,14 @@
+use cosmwasm_std::{Addr, Api, CanonicalAddr, StdResult};
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+/// Address of the account controlled by a secp256k1 public key, derived like Cosmos SDK
+/// accounts: ripemd160(sha256(pubkey))
+pub fn secp256k1_address(api: &dyn Api, pubkey: &[u8]) -> StdResult<Addr> {
+    let hash = Ripemd160::digest(&sha256(pubkey)).to_vec();
+    api.addr_humanize(&CanonicalAddr::from(hash))
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
 
 // TODO finish implementation of custom errors
@@ -10,11 +10,143 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Caller is not the contract admin")]
+    NotAdmin {},
+
+    #[error("Caller is not the pending admin")]
+    NotPendingAdmin {},
+
     #[error("token_id already claimed")]
     Claimed {},
 
     #[error("Cannot set approval that is already expired")]
     Expired {},
+
+    #[error("Must send exactly {expected}")]
+    InvalidFunds { expected: String },
+
+    #[error("Price must be greater than zero")]
+    InvalidPrice {},
+
+    #[error("Royalty share must be between 0 and 1")]
+    InvalidRoyalty {},
+
+    #[error("Token is not listed for sale")]
+    NotListed {},
+
+    #[error("Listing is stale: seller no longer owns the token")]
+    StaleListing {},
+
+    #[error("Token is exclusively licensed")]
+    ExclusivelyLicensed {},
+
+    #[error("Exclusive licenses can't be sold while other licenses are valid")]
+    LicensesOutstanding {},
+
+    #[error("Granted licenses weren't paid through the contract and can't be refunded")]
+    NotRefundable {},
+
+    #[error("Token transfers are locked by an exclusive license")]
+    TransferLocked {},
+
+    #[error("All licenses for this token have been sold")]
+    SoldOut {},
+
+    #[error("License expiration is already in the past")]
+    LicenseExpired {},
+
+    #[error("License has been revoked")]
+    LicenseRevoked {},
+
+    #[error("Licensee already holds a valid license")]
+    AlreadyLicensed {},
+
+    #[error("Batch must contain at least one item")]
+    EmptyBatch {},
+
+    #[error("Batch cannot contain more than {max} items")]
+    BatchTooLarge { max: u32 },
+
+    #[error("No license payment held in escrow")]
+    NoEscrow {},
+
+    #[error("A license payment is still held in escrow")]
+    EscrowPending {},
+
+    #[error("Refund window has closed")]
+    RefundWindowClosed {},
+
+    #[error("Refund window is still open")]
+    RefundWindowOpen {},
+
+    #[error("An auction is already running for this token")]
+    AuctionExists {},
+
+    #[error("No auction is running for this token")]
+    NoAuction {},
+
+    #[error("Auction end must be a height or time in the future")]
+    InvalidAuctionEnd {},
+
+    #[error("Auction has ended")]
+    AuctionEnded {},
+
+    #[error("Auction has not ended yet")]
+    AuctionNotEnded {},
+
+    #[error("Auction has bids and cannot be cancelled")]
+    AuctionHasBids {},
+
+    #[error("Bid must be at least {min_bid}")]
+    BidTooLow { min_bid: Coin },
+
+    #[error("Token is under dispute")]
+    Disputed {},
+
+    #[error("No dispute is open for this token")]
+    NoDispute {},
+
+    #[error("No arbitrators are configured to resolve disputes")]
+    NoArbitrators {},
+
+    #[error("Bond must be a positive amount of at least the minimum dispute bond")]
+    InvalidBond {},
+
+    #[error("Token is suspended")]
+    Suspended {},
+
+    #[error("Operation is paused")]
+    Paused {},
+
+    #[error("No deposit is held for this token")]
+    NoDeposit {},
+
+    #[error("Deposit can't be reclaimed before the clean period ends")]
+    DepositLocked {},
+
+    #[error("Deposits can't be forfeited without an admin to receive them")]
+    NoForfeitRecipient {},
+
+    #[error("Tokens with licenses on record can't be burned")]
+    LicensesOnRecord {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Public key must be a 33 byte compressed secp256k1 key")]
+    InvalidPubkey {},
+
+    #[error("Invalid authorship proof")]
+    InvalidAuthorship {},
+
+    #[error("Voucher was issued for another contract")]
+    WrongContract {},
+
+    #[error("Voucher has expired")]
+    VoucherExpired {},
+
+    #[error("Voucher nonce has already been used")]
+    NonceUsed {},
 }
 
 impl From<cw721_base::ContractError> for ContractError {
@@ -0,0 +1,41 @@
+use std::convert::TryFrom;
+
+/// Symbols of the base58-iscc encoding used by ISCC v1 codes
+const SYMBOLS: &str = "C23456789rB1ZEFGTtYiAaVNLcPfbHRdSqhDUeWKmjMnQzXokxyvgsuw";
+
+/// A decoded ISCC code: one header byte identifying the unit, followed by a 64 bit body
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IsccCode {
+    pub header: u8,
+    pub body: u64,
+}
+
+impl IsccCode {
+    /// Decodes a 13 character code, `None` if it isn't valid base58-iscc
+    pub fn decode(code: &str) -> Option<Self> {
+        if code.len() != 13 || !code.is_ascii() {
+            return None;
+        }
+        let header = decode_chunk(&code[..2])?;
+        let body = decode_chunk(&code[2..])?;
+        Some(IsccCode {
+            header: u8::try_from(header).ok()?,
+            body: u64::try_from(body).ok()?,
+        })
+    }
+
+    /// Hamming distance between the bodies of two codes of the same unit
+    pub fn distance(&self, other: &IsccCode) -> Option<u32> {
+        if self.header != other.header {
+            return None;
+        }
+        Some((self.body ^ other.body).count_ones())
+    }
+}
+
+fn decode_chunk(chunk: &str) -> Option<u128> {
+    chunk.chars().try_fold(0u128, |value, c| {
+        let digit = SYMBOLS.find(c)? as u128;
+        Some(value * 58 + digit)
+    })
+}
@@ -1,6 +1,11 @@
 pub mod contract;
+#[cfg(test)]
+mod contract_tests;
+mod crypto;
 mod error;
+mod iscc;
 pub mod msg;
+mod pagination;
 pub mod state;
 
 pub use crate::error::ContractError;
@@ -1,6 +1,11 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Addr, Binary, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
+use crate::state::{
+    Authorship, BlockStamp, ExclusiveTerms, License, Listing, MintDeposit, ModerationEntry, PauseFlags,
+    ReasonCode,
+};
+use cw0::Duration;
 use cw721::Expiration;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -9,6 +14,20 @@ pub struct InstantiateMsg {
     pub name: String,
     /// Symbol of the NFT contract
     pub symbol: String,
+    /// Registry operator (defaults to the instantiator)
+    pub admin: Option<String>,
+    /// Accounts allowed to moderate tokens
+    pub moderators: Option<Vec<String>>,
+    /// Maximum number of tokens minted by a single `MintBatch` (defaults to 50)
+    pub max_mint_batch: Option<u32>,
+    /// Accounts allowed to resolve disputes
+    pub arbitrators: Option<Vec<String>>,
+    /// Minimum bond to open a dispute
+    pub min_dispute_bond: Option<Coin>,
+    /// Deposit required to mint a token
+    pub mint_deposit: Option<MintDeposit>,
+    /// Non-refundable fee charged for every minted token
+    pub mint_fee: Option<MintFeeMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -37,14 +56,64 @@ pub struct MintMsg {
     pub license_url: String,
     /// Price to license 
     pub license_price: Coin,
+    /// Makes every license sold exclusive (optional)
+    pub license_exclusive: Option<ExclusiveTerms>,
+    /// Maximum number of licenses that can be sold (optional)
+    pub max_licenses: Option<u64>,
+    /// Period during which licensees can ask for a refund (optional)
+    pub refund_window: Option<Duration>,
+    /// Royalty paid on every sale of the token (optional)
+    pub royalty: Option<RoyaltyMsg>,
+    /// Proof that the author's key signed the ISCC codes (optional)
+    pub authorship: Option<Authorship>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyMsg {
+    /// Address receiving the royalties
+    pub recipient: String,
+    /// Share of the sale price paid as royalty (between 0 and 1)
+    pub share: Decimal,
+}
+
+/// Mint signed off-chain by the creator, redeemed by the first licensee
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintVoucher {
+    /// Compressed secp256k1 public key of the creator (33 bytes), the token is minted to its
+    /// account
+    pub creator_pubkey: Binary,
+    /// Contract the voucher can be redeemed on
+    pub contract_address: String,
+    /// Single use number, chosen by the creator
+    pub nonce: u64,
+    /// When the voucher can no longer be redeemed (optional)
+    pub expires: Option<Expiration>,
+    pub mint: MintMsg,
+}
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintFeeMsg {
+    pub amount: Coin,
+    /// Address the fees are forwarded to
+    pub treasury: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LicenseItem {
+    pub token_id: String,
+    /// Defaults to the sender
+    pub licensee: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Mint a new NFT, can only be called by the contract minter
     Mint(MintMsg),
+
+    /// Mint many NFTs at once, fails as a whole if any of them cannot be minted
+    MintBatch(Vec<MintMsg>),
     
     /// Transfer is a base message to move a token to another account without triggering actions
     TransferNft { recipient: String, token_id: String },
@@ -78,9 +147,186 @@ pub enum ExecuteMsg {
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
     
-    /// Buy a license
+    /// Buy a license, for the sender or on behalf of another licensee
     License {
         token_id: String,
+        licensee: Option<String>,
+    },
+
+    /// Buy licenses of many tokens at once, the sum of all prices must be sent.
+    /// Fails as a whole if any license cannot be bought
+    LicenseBatch {
+        items: Vec<LicenseItem>,
+    },
+
+    /// Record a license negotiated off-chain, no payment is made through the contract.
+    /// Can only be called by the owner or an approved operator
+    GrantLicense {
+        token_id: String,
+        licensee: String,
+        /// Declared price of the deal, zero if unset
+        price: Option<Coin>,
+        /// Defaults to the exclusive terms duration, or never for non-exclusive licensing
+        expires: Option<Expiration>,
+        memo: Option<String>,
+    },
+
+    /// Revoke a license, e.g. for a breach of terms. Can only be called by the owner.
+    /// Refunds come from the escrowed payment, otherwise the owner must send the price
+    /// paid for the license
+    RevokeLicense {
+        token_id: String,
+        licensee: String,
+        reason: String,
+        refund: bool,
+    },
+
+    /// Replace the licensing terms of a token, can only be called by the owner
+    UpdateLicensing {
+        token_id: String,
+        url: String,
+        price: Coin,
+        exclusive: Option<ExclusiveTerms>,
+        max_licenses: Option<u64>,
+        refund_window: Option<Duration>,
+    },
+
+    /// Get the license payment back while the refund window is open, giving up the license
+    RequestRefund {
+        token_id: String,
+    },
+
+    /// Pay an escrowed license payment to the owner once the refund window has closed
+    ReleaseEscrow {
+        token_id: String,
+        licensee: String,
+    },
+
+    /// Offer the token for sale at a fixed price, can only be called by the owner
+    ListForSale {
+        token_id: String,
+        price: Coin,
+    },
+
+    /// Withdraw the sale listing of a token
+    CancelListing {
+        token_id: String,
+    },
+
+    /// Buy a listed token, the exact listing price must be sent
+    Buy {
+        token_id: String,
+    },
+
+    /// Start an English auction for an exclusive license of the token,
+    /// can only be called by the owner
+    StartAuction {
+        token_id: String,
+        /// Minimum amount accepted for the first bid
+        reserve_price: Coin,
+        /// Minimum amount by which a bid must exceed the highest bid
+        min_increment: Uint128,
+        /// When the auction ends (block height or time)
+        end: Expiration,
+        /// Duration of the exclusive license granted to the winner
+        license_duration: Duration,
+    },
+
+    /// Bid on a running auction, funds are held until outbid or settlement
+    PlaceBid {
+        token_id: String,
+    },
+
+    /// Close an ended auction, granting the exclusive license to the highest bidder
+    SettleAuction {
+        token_id: String,
+    },
+
+    /// Cancel an auction that has not received any bids
+    CancelAuction {
+        token_id: String,
+    },
+
+    /// Claim authorship of a registered work, freezing its licensing until an arbitrator
+    /// resolves the dispute. The bond must be sent along
+    OpenDispute {
+        token_id: String,
+        evidence_uri: String,
+        bond: Coin,
+    },
+
+    /// Rule on an open dispute (arbitrators only)
+    ResolveDispute {
+        token_id: String,
+        ruling: DisputeRuling,
+    },
+
+    /// Set the moderation flags of a token (admin and moderators only).
+    /// Clearing both flags reinstates the token
+    Moderate {
+        token_id: String,
+        hidden: bool,
+        suspended: bool,
+        reason: ReasonCode,
+        /// Sends the token's mint deposit to the admin instead of its depositor
+        forfeit_deposit: Option<bool>,
+    },
+
+    /// Get the mint deposit of a token back once its clean period ended (depositor only)
+    ReclaimDeposit {
+        token_id: String,
+    },
+
+    /// Destroy a token, freeing its ISCC code and refunding its mint deposit to the depositor.
+    /// Tokens with licenses on record can't be burned
+    Burn {
+        token_id: String,
+    },
+
+    /// Change the deposit required to mint a token (admin only)
+    SetMintDeposit {
+        mint_deposit: Option<MintDeposit>,
+    },
+
+    /// Change the fee charged for minting a token and where it goes (admin only)
+    SetMintFee {
+        mint_fee: Option<MintFeeMsg>,
+    },
+
+    /// Propose a new admin, who takes over once it accepts (admin only)
+    ProposeAdmin {
+        admin: String,
+    },
+
+    /// Become the admin (proposed admin only)
+    AcceptAdmin {},
+
+    /// Give up the admin role for good, leaving the admin settings frozen (admin only)
+    RenounceAdmin {},
+
+    /// Pause or resume groups of operations (admin only)
+    SetPause(PauseFlags),
+
+    /// Mint a token from a creator's voucher and buy its first license at once.
+    /// `signature` is the creator's secp256k1 signature over the sha256 hash of the
+    /// voucher serialized as compact JSON, fields in declaration order and unset ones
+    /// as null. The sender pays the license price, plus the mint fee and deposit, if any
+    MintAndLicense {
+        voucher: MintVoucher,
+        signature: Binary,
+        licensee: Option<String>,
+    },
+
+    /// Add or remove moderators (admin only)
+    UpdateModerators {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    /// Add or remove the arbitrators ruling on disputes (admin only)
+    UpdateArbitrators {
+        add: Vec<String>,
+        remove: Vec<String>,
     },
 }
 
@@ -102,7 +348,9 @@ pub enum QueryMsg {
         /// unset or false will filter out expired items, you must set to true to see them
         include_expired: Option<bool>,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     },
 
     /// Total number of tokens issued
@@ -112,6 +360,10 @@ pub enum QueryMsg {
     /// Returns top-level metadata about the contract: `ContractInfoResponse`
     ContractInfo {},
 
+    /// Returns the contract configuration
+    /// Return type: Config
+    Config {},
+
     /// With MetaData Extension.
     /// Returns metadata about one particular token, based on *ERC721 Metadata JSON Schema*
     /// but directly from the contract: `NftInfoResponse`
@@ -133,23 +385,223 @@ pub enum QueryMsg {
     /// Return type: TokensResponse.
     Tokens {
         owner: String,
+        /// unset or false will filter out hidden tokens, you must set to true to see them
+        include_hidden: Option<bool>,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     },
 
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
     /// Return type: TokensResponse.
     AllTokens {
+        /// unset or false will filter out hidden tokens, you must set to true to see them
+        include_hidden: Option<bool>,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     },
 
     /// Resolve a nft token by content id (iscc)
     /// Return type: TokenResponse
     GetByContentId {
         content_id: String,
-    }
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+
+    /// Returns the earliest registration of an ISCC unit. With `max_distance`, codes of the
+    /// same unit type within that hamming distance also match. Such similarity searches compare
+    /// against at most `limit` registrations after `start_after`, in token id order
+    /// Return type: EarliestRegistrationResponse
+    EarliestRegistration {
+        unit: IsccUnit,
+        max_distance: Option<u32>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns a token with its ISCC and licensing data
+    /// Return type: TokenResponse
+    Token {
+        token_id: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+
+    /// Same as `Tokens`, with the full data of every token
+    /// Return type: TokensInfoResponse
+    TokensInfo {
+        owner: String,
+        include_expired: Option<bool>,
+        include_hidden: Option<bool>,
+        start_after: Option<String>,
+        end_before: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Same as `AllTokens`, with the full data of every token
+    /// Return type: TokensInfoResponse
+    AllTokensInfo {
+        include_expired: Option<bool>,
+        include_hidden: Option<bool>,
+        start_after: Option<String>,
+        end_before: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Lists the licenses held by the licensee, ordered by token id
+    /// Return type: LicensesResponse
+    Licenses {
+        licensee: String,
+        start_after: Option<String>,
+        end_before: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Lists the licenses of a token, ordered by licensee
+    /// Return type: LicensesResponse
+    TokenLicenses {
+        token_id: String,
+        start_after: Option<String>,
+        end_before: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Lists all tokens offered for sale
+    /// Return type: ListingsResponse
+    Listings {
+        /// unset or false will filter out listings of hidden tokens
+        include_hidden: Option<bool>,
+        start_after: Option<String>,
+        end_before: Option<String>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Returns the running license auction of a token
+    /// Return type: Auction
+    Auction {
+        token_id: String,
+    },
+
+    /// Returns the active exclusive license of a token, if any
+    /// Return type: Option<Exclusivity>
+    Exclusivity {
+        token_id: String,
+    },
+
+    /// Checks if the licensee holds a valid (neither revoked nor expired) license
+    /// Return type: IsLicensedResponse
+    IsLicensed {
+        token_id: String,
+        licensee: String,
+    },
+
+    /// Returns the license payment held in escrow, if any
+    /// Return type: Option<Escrow>
+    Escrow {
+        token_id: String,
+        licensee: String,
+    },
+
+    /// Returns the funds required to buy a license right now, how they are paid out,
+    /// and why the purchase would fail, if it would
+    /// Return type: QuoteResponse
+    QuoteLicense {
+        token_id: String,
+        licensee: Option<String>,
+    },
+
+    /// Returns the number of licenses sold and still available for a token
+    /// Return type: LicenseSupplyResponse
+    LicenseSupply {
+        token_id: String,
+    },
+
+    /// Returns registry wide statistics
+    /// Return type: StatsResponse
+    Stats {},
+
+    /// Returns the open dispute of a token, if any
+    /// Return type: Option<Dispute>
+    Dispute {
+        token_id: String,
+    },
+
+    /// Returns the mint deposit held for a token, if any
+    /// Return type: Option<Deposit>
+    Deposit {
+        token_id: String,
+    },
+
+    /// Returns the total of the mint deposits held by the contract
+    /// Return type: DepositHoldingsResponse
+    DepositHoldings {},
+
+    /// Returns the current and proposed admin
+    /// Return type: AdminResponse
+    Admin {},
+
+    /// Returns which operations are paused
+    /// Return type: PauseFlags
+    PauseStatus {},
+
+    /// Returns the moderation flags of a token, if any
+    /// Return type: Option<Moderation>
+    Moderation {
+        token_id: String,
+    },
+
+    /// Lists moderation actions, optionally for a single token, ordered by id
+    /// Return type: ModerationLogResponse
+    ModerationLog {
+        token_id: Option<String>,
+        start_after: Option<u64>,
+        end_before: Option<u64>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+
+    /// Returns the licensing statistics of a token
+    /// Return type: TokenStatsResponse
+    TokenStats {
+        token_id: String,
+    },
+}
+
+/// ISCC unit to look registrations up by
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IsccUnit {
+    ContentId(String),
+    DataId(String),
+    InstanceId(String),
+}
+
+/// Outcome of a dispute
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeRuling {
+    /// The token and its ISCC claim go to the claimant, who gets the bond back
+    Claimant,
+    /// The owner keeps the token and receives the bond
+    Owner,
+}
+
+/// Iteration order of list queries
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderBy {
+    Asc,
+    Desc,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -163,6 +615,143 @@ pub struct TokenResponse {
     pub content_id: String,
     pub data_id: String,
     pub instance_id: String,
+    pub tophash: String,
+    pub registered_at: BlockStamp,
+    /// Verified authorship proof, if one was given at mint
+    pub authorship: Option<Authorship>,
+    pub approvals: Vec<cw721::Approval>,
     pub license_url: String,
     pub license_price: Coin,
+    pub licensing_status: LicensingStatus,
+    pub licenses_sold: u64,
+    /// Number of licenses still available (unlimited if unset)
+    pub licenses_remaining: Option<u64>,
+}
+
+/// Whether licenses of a token can be bought at the moment
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LicensingStatus {
+    Available,
+    /// An active exclusive license locks out other licensees
+    Exclusive,
+    /// Exclusive licenses can't be sold while other licenses are still valid
+    LicensesOutstanding,
+    SoldOut,
+    /// An exclusive license is being auctioned
+    Auctioned,
+    /// Licensing is frozen while an authorship dispute is open
+    Disputed,
+    /// A moderator suspended the token
+    Suspended,
+    /// The admin paused licensing
+    Paused,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokensInfoResponse {
+    pub tokens: Vec<TokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LicensesResponse {
+    pub licenses: Vec<License>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ModerationLogResponse {
+    pub entries: Vec<ModerationEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ListingsResponse {
+    pub listings: Vec<Listing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LicenseSupplyResponse {
+    pub max_licenses: Option<u64>,
+    pub sold: u64,
+    pub remaining: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct EarliestRegistrationResponse {
+    /// Earliest matching registration among the ones searched
+    pub registration: Option<RegistrationMatch>,
+    /// Set when a similarity search reached its limit, pass it as `start_after` to search the
+    /// following registrations
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RegistrationMatch {
+    pub token_id: String,
+    pub registrant: Addr,
+    pub registered_at: BlockStamp,
+    /// Code of the matching unit
+    pub code: String,
+    /// Hamming distance to the requested code, zero for exact matches
+    pub distance: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DepositHoldingsResponse {
+    /// Deposit currently required to mint a token
+    pub mint_deposit: Option<MintDeposit>,
+    /// Total of the deposits held, per denom
+    pub held: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AdminResponse {
+    pub admin: Option<Addr>,
+    pub pending_admin: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StatsResponse {
+    pub total_tokens: u64,
+    /// Licenses issued, including granted and auctioned ones, refunded ones excepted
+    pub licenses_sold: u64,
+    /// Funds paid for licenses through the contract, per denom
+    pub revenue: Vec<Coin>,
+    /// Number of addresses currently owning at least one token
+    pub owners: u64,
+    /// Number of addresses with a license on record, revoked ones included
+    pub licensees: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenStatsResponse {
+    pub token_id: String,
+    pub licenses_sold: u64,
+    pub revenue: Vec<Coin>,
+    pub last_license_height: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct IsLicensedResponse {
+    pub licensed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct QuoteResponse {
+    /// Exact funds to send with the purchase
+    pub funds: Vec<Coin>,
+    /// How the funds are distributed
+    pub payouts: Vec<Payout>,
+    /// Set when the payment is held in escrow until the refund window closes
+    pub escrow_until: Option<Expiration>,
+    /// When the license would expire
+    pub expires: Expiration,
+    pub exclusive: bool,
+    /// Reason the purchase would fail, if any
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Payout {
+    pub recipient: Addr,
+    pub amount: Coin,
 }
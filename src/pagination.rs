@@ -0,0 +1,62 @@
+use cosmwasm_std::{Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::msg::OrderBy;
+
+// used for limiting queries
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+
+// used for limiting the registrations compared by similarity searches
+pub const DEFAULT_SCAN_LIMIT: u32 = 100;
+pub const MAX_SCAN_LIMIT: u32 = 500;
+
+/// Pagination arguments shared by all list queries
+#[derive(Default)]
+pub struct Pagination<K = String> {
+    /// Exclusive key the page starts after, in iteration order
+    pub start_after: Option<K>,
+    /// Exclusive key the page ends before, in iteration order
+    pub end_before: Option<K>,
+    pub limit: Option<u32>,
+    pub order: Option<OrderBy>,
+}
+
+impl<K> Pagination<K> {
+    /// Number of items in the page, capped to `MAX_LIMIT`
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+    }
+
+    /// Range bounds and order to iterate the page with, `to_key` maps the
+    /// `start_after` / `end_before` values to raw storage keys
+    pub fn range<F>(&self, to_key: F) -> StdResult<(Option<Bound>, Option<Bound>, Order)>
+    where
+        F: Fn(&K) -> StdResult<Vec<u8>>,
+    {
+        let start = match &self.start_after {
+            Some(key) => Some(Bound::Exclusive(to_key(key)?)),
+            None => None,
+        };
+        let end = match &self.end_before {
+            Some(key) => Some(Bound::Exclusive(to_key(key)?)),
+            None => None,
+        };
+
+        // descending ranges iterate from the upper bound down to the lower one
+        match self.order.unwrap_or(OrderBy::Asc) {
+            OrderBy::Asc => Ok((start, end, Order::Ascending)),
+            OrderBy::Desc => Ok((end, start, Order::Descending)),
+        }
+    }
+}
+
+/// Maps a string key to its raw storage key
+pub fn raw_key(key: &String) -> StdResult<Vec<u8>> {
+    Ok(key.as_bytes().to_vec())
+}
+
+/// Maps a numeric id to its raw storage key
+pub fn id_key(id: &u64) -> StdResult<Vec<u8>> {
+    Ok(id.to_be_bytes().to_vec())
+}
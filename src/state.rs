@@ -1,8 +1,56 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Addr, Binary, BlockInfo};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, U64Key};
+use cosmwasm_std::{Coin, Decimal, Timestamp, Uint128};
+use cw0::{Duration, Expiration};
+
+/// Contract configuration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Registry operator, manages the contract settings. Unset once renounced
+    pub admin: Option<Addr>,
+    /// Account proposed as the next admin, until it accepts
+    pub pending_admin: Option<Addr>,
+    /// Accounts allowed to moderate tokens, besides the admin
+    pub moderators: Vec<Addr>,
+    /// Maximum number of tokens minted by a single `MintBatch`
+    pub max_mint_batch: u32,
+    /// Accounts allowed to resolve disputes
+    pub arbitrators: Vec<Addr>,
+    /// Minimum bond to open a dispute
+    pub min_dispute_bond: Option<Coin>,
+    /// Deposit required to mint a token
+    pub mint_deposit: Option<MintDeposit>,
+    /// Non-refundable fee charged for every minted token
+    pub mint_fee: Option<MintFee>,
+}
+
+/// Fee charged for every minted token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintFee {
+    pub amount: Coin,
+    /// Address the fees are forwarded to
+    pub treasury: Addr,
+}
+
+/// Refundable deposit held for every minted token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintDeposit {
+    pub amount: Coin,
+    /// How long the token must stay clean before the deposit can be reclaimed, burning the
+    /// token refunds it right away
+    pub clean_period: Duration,
+}
+
+/// Deposit held for a token until it is reclaimed, refunded on burn or forfeited
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Deposit {
+    pub token_id: String,
+    pub depositor: Addr,
+    pub amount: Coin,
+    pub refundable_at: Expiration,
+}
 
 /// ISCC data derived from the media asset
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -13,6 +61,46 @@ pub struct IsccData {
     pub data_id: String,   
     pub instance_id: String, 
     pub tophash: String,
+    /// Account that minted the token
+    pub registrant: Addr,
+    /// Block the token was registered in
+    pub registered_at: BlockStamp,
+    /// Proof, verified at mint, that the author's key signed the ISCC codes
+    pub authorship: Option<Authorship>,
+}
+
+/// Signature of the author over the full ISCC code of the work, that is the meta, content,
+/// data and instance ids joined with `-`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Authorship {
+    pub scheme: SignatureScheme,
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// Signature over the sha256 hash of the code, compressed or uncompressed public key
+    Secp256k1,
+    /// Signature over the code itself
+    Ed25519,
+}
+
+/// Block height and time an event was recorded at
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockStamp {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+impl From<&BlockInfo> for BlockStamp {
+    fn from(block: &BlockInfo) -> Self {
+        BlockStamp {
+            height: block.height,
+            time: block.time,
+        }
+    }
 }
 
 /// Licensing data: price for licensing the token
@@ -21,6 +109,21 @@ pub struct Licensing {
     pub token_id: String,
     pub url: String,
     pub price: Coin,
+    /// Set when every license sold is exclusive
+    pub exclusive: Option<ExclusiveTerms>,
+    /// Maximum number of licenses that can be sold (unlimited if unset)
+    pub max_licenses: Option<u64>,
+    /// Period during which payments are held in escrow and licensees can ask for a refund
+    pub refund_window: Option<Duration>,
+}
+
+/// Terms of exclusive licenses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExclusiveTerms {
+    /// How long the exclusive license lasts
+    pub duration: Duration,
+    /// Whether ownership transfers are blocked while the license is active
+    pub lock_transfer: bool,
 }
 
 /// License transaction
@@ -28,17 +131,279 @@ pub struct Licensing {
 pub struct License {
     pub token_id: String,
     pub licensee: Addr,
+    /// Account that paid for the license, may differ from the licensee
+    pub payer: Addr,
     pub price: Coin,
+    /// When the license expires (never for perpetual licenses)
+    pub expires: Expiration,
+    /// Exclusive licenses block other license sales until they expire
+    pub exclusive: bool,
+    /// Granted by the rights holder for a deal made off-chain
+    pub granted: bool,
+    /// Reference to the off-chain deal of a granted license
+    pub memo: Option<String>,
+    /// Set once the rights holder revokes the license
+    pub revoked: Option<Revocation>,
+    /// Block the license was bought or granted in
+    pub purchased_at: BlockStamp,
 }
 
-/// maps content_id to iscc data
-pub const ISCC_DATA: Map<&str, IsccData> = Map::new("iscc_data");
+impl License {
+    /// A license is valid until it is revoked or expires
+    pub fn is_valid(&self, block: &BlockInfo) -> bool {
+        self.revoked.is_none() && !self.expires.is_expired(block)
+    }
+}
+
+/// Revocation of a license by the rights holder
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Revocation {
+    pub reason: String,
+    /// Whether the price paid was refunded
+    pub refunded: bool,
+}
+
+/// Exclusive license currently held on a token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Exclusivity {
+    pub licensee: Addr,
+    pub expires: Expiration,
+    pub lock_transfer: bool,
+}
+
+/// License payment held by the contract until the refund window closes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Escrow {
+    pub token_id: String,
+    pub licensee: Addr,
+    pub payer: Addr,
+    /// Token owner at the time of the purchase, receives the funds on release
+    pub payee: Addr,
+    pub amount: Coin,
+    pub release_at: Expiration,
+}
+
+/// Bid placed in an auction, the funds are held by the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+    pub bidder: Addr,
+    pub amount: Coin,
+}
+
+/// English auction for an exclusive license of the token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    pub token_id: String,
+    pub seller: Addr,
+    pub reserve_price: Coin,
+    pub min_increment: Uint128,
+    pub end: Expiration,
+    /// Duration of the exclusive license granted to the winner
+    pub license_duration: Duration,
+    pub highest_bid: Option<Bid>,
+}
+
+/// Royalty owed on every sale of the token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Royalty {
+    pub recipient: Addr,
+    pub share: Decimal,
+}
+
+/// Fixed-price offer to sell the token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Listing {
+    pub token_id: String,
+    pub seller: Addr,
+    pub price: Coin,
+}
+
+/// Claim that a token was registered by someone other than the author
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Dispute {
+    pub token_id: String,
+    pub claimant: Addr,
+    /// Where the evidence backing the claim can be found
+    pub evidence_uri: String,
+    /// Returned to the claimant if the claim is upheld, paid to the owner otherwise
+    pub bond: Coin,
+    pub opened_at: BlockStamp,
+}
+
+/// Operations suspended by the admin during an incident
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PauseFlags {
+    /// `Mint` and `MintBatch`
+    pub mint: bool,
+    /// License sales, grants and auctions
+    pub license: bool,
+    /// `TransferNft`, `SendNft`, `Buy` and `Burn`
+    pub transfer: bool,
+    /// Token and operator approvals
+    pub approvals: bool,
+}
+
+/// Why a moderator acted on a token
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    Infringement,
+    IllegalContent,
+    Fraud,
+    Spam,
+    Other,
+    /// The token was cleared and its flags lifted
+    Reinstated,
+}
+
+/// Current moderation flags of a token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Moderation {
+    /// Hidden tokens are left out of list queries unless asked for
+    pub hidden: bool,
+    /// Suspended tokens can't be licensed or transferred
+    pub suspended: bool,
+    pub reason: ReasonCode,
+    pub moderator: Addr,
+    pub updated_at: BlockStamp,
+}
+
+/// Moderation log entry
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ModerationEntry {
+    pub id: u64,
+    pub token_id: String,
+    pub hidden: bool,
+    pub suspended: bool,
+    pub reason: ReasonCode,
+    pub moderator: Addr,
+    pub at: BlockStamp,
+}
+
+/// Registry wide statistics, maintained as tokens are minted, transferred and licensed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stats {
+    /// Licenses issued, including granted and auctioned ones, refunded ones excepted
+    pub licenses_sold: u64,
+    /// Funds paid for licenses through the contract, per denom
+    pub revenue: Vec<Coin>,
+    /// Number of addresses currently owning at least one token
+    pub owners: u64,
+    /// Number of addresses with a license on record, revoked ones included
+    pub licensees: u64,
+}
+
+/// Licensing statistics of a single token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TokenStats {
+    /// Funds paid for licenses of the token, per denom
+    pub revenue: Vec<Coin>,
+    pub last_license_height: Option<u64>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const PAUSE: Item<PauseFlags> = Item::new("pause");
+
+pub const STATS: Item<Stats> = Item::new("stats");
+
+/// maps token id to its licensing statistics, the licenses sold are kept in `LICENSES_SOLD`
+pub const TOKEN_STATS: Map<&str, TokenStats> = Map::new("token_stats");
+
+pub struct IsccDataIndexes<'a> {
+    // pk goes to second tuple element
+    pub data: MultiIndex<'a, (String, Vec<u8>), IsccData>,
+    pub instance: MultiIndex<'a, (String, Vec<u8>), IsccData>,
+}
+
+impl<'a> IndexList<IsccData> for IsccDataIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<IsccData>> + '_> {
+        let v: Vec<&dyn Index<IsccData>> = vec![&self.data, &self.instance];
+        Box::new(v.into_iter())
+    }
+}
+
+/// maps token id to iscc data, indexed by data id and instance id
+pub fn iscc_data<'a>() -> IndexedMap<'a, &'a str, IsccData, IsccDataIndexes<'a>> {
+    let indexes = IsccDataIndexes {
+        data: MultiIndex::new(
+            |d: &IsccData, k: Vec<u8>| (d.data_id.clone(), k),
+            "iscc_data",
+            "iscc_data__data",
+        ),
+        instance: MultiIndex::new(
+            |d: &IsccData, k: Vec<u8>| (d.instance_id.clone(), k),
+            "iscc_data",
+            "iscc_data__instance",
+        ),
+    };
+    IndexedMap::new("iscc_data", indexes)
+}
 
 /// maps content_id (from iscc code) to token Id
 pub const ISCC: Map<&str, String> = Map::new("iscc");
 
+/// maps token id to its open dispute
+pub const DISPUTES: Map<&str, Dispute> = Map::new("disputes");
+
+/// maps token id to the mint deposit held for it
+pub const DEPOSITS: Map<&str, Deposit> = Map::new("deposits");
+
+/// total of the mint deposits held, per denom
+pub const DEPOSITS_HELD: Item<Vec<Coin>> = Item::new("deposits_held");
+
+/// maps token id to its moderation flags, unflagged tokens have no entry
+pub const MODERATION: Map<&str, Moderation> = Map::new("moderation");
+
+/// every moderation action, by sequential id
+pub const MODERATION_LOG: Map<U64Key, ModerationEntry> = Map::new("moderation_log");
+
+pub const MODERATION_COUNT: Item<u64> = Item::new("moderation_count");
+
+/// voucher nonces used by each creator
+pub const VOUCHER_NONCES: Map<(&Addr, U64Key), bool> = Map::new("voucher_nonces");
+
 /// maps token id to licensing data
 pub const LICENSING: Map<&str, Licensing> = Map::new("license");
 
-/// maps licensee address + token id to license 
-pub const LICENSE: Map<(&Addr, &str), License> = Map::new("license");
+pub struct LicenseIndexes<'a> {
+    // pk goes to second tuple element
+    pub token: MultiIndex<'a, (String, Vec<u8>), License>,
+}
+
+impl<'a> IndexList<License> for LicenseIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<License>> + '_> {
+        let v: Vec<&dyn Index<License>> = vec![&self.token];
+        Box::new(v.into_iter())
+    }
+}
+
+/// maps licensee address + token id to license, indexed by token id
+pub fn licenses<'a>() -> IndexedMap<'a, (&'a Addr, &'a str), License, LicenseIndexes<'a>> {
+    let indexes = LicenseIndexes {
+        token: MultiIndex::new(
+            |d: &License, k: Vec<u8>| (d.token_id.clone(), k),
+            "licenses",
+            "licenses__token",
+        ),
+    };
+    IndexedMap::new("licenses", indexes)
+}
+
+/// maps token id to royalty data
+pub const ROYALTIES: Map<&str, Royalty> = Map::new("royalties");
+
+/// maps token id to its sale listing
+pub const LISTINGS: Map<&str, Listing> = Map::new("listings");
+
+/// maps licensee address + token id to the escrowed license payment
+pub const ESCROWS: Map<(&Addr, &str), Escrow> = Map::new("escrows");
+
+/// maps token id to the number of licenses sold
+pub const LICENSES_SOLD: Map<&str, u64> = Map::new("licenses_sold");
+
+/// maps token id to its running license auction
+pub const AUCTIONS: Map<&str, Auction> = Map::new("auctions");
+
+/// maps token id to the exclusive license held on it
+pub const EXCLUSIVITY: Map<&str, Exclusivity> = Map::new("exclusivity");